@@ -0,0 +1,545 @@
+//! Reusable reductions over traversals.
+//!
+//! A `Collector` describes how to reduce a traversal to a single value, so
+//! it can be built once, passed around and combined before being run with
+//! `Traversal::collect_with`. A tuple of collectors is itself a collector,
+//! which runs them all in one pass:
+//!
+//! ```
+//! use traverse::Traversal;
+//! use traverse::collector::{count, sum, max};
+//! use traverse::utils::range;
+//!
+//! let (n, total, largest) = range(1, 5).collect_with((count(), sum(), max()));
+//! assert_eq!((n, total, largest), (4, 10, Some(4)));
+//! ```
+
+use std::marker::PhantomData;
+use std::mem;
+use {FromTraversal, Internal};
+
+use num::traits::Zero;
+
+/// A reduction of a sequence of `T`s into a single value.
+///
+/// The collector itself holds the configuration, such as closures, while
+/// the accumulator made by `init` holds the running state.
+///
+/// Most collectors keep constant state, but some cost more:
+///
+/// - `from_traversal` buffers every element in a `Vec` and only builds the
+///   collection in `finish`.
+/// - A tuple of collectors needs `T: Clone`, and clones each element once
+///   for every member but the last, which gets it by move.
+pub trait Collector<T> {
+    /// The running state of the reduction.
+    type Acc;
+
+    /// The result of the reduction.
+    type Output;
+
+    /// Create the state before any element is seen.
+    fn init(&mut self) -> Self::Acc;
+
+    /// Fold one element into the state.
+    ///
+    /// Return true once the result can no longer change, so the
+    /// traversal may stop early.
+    fn accumulate(&mut self, acc: &mut Self::Acc, item: T) -> bool;
+
+    /// Turn the final state into the result.
+    fn finish(self, acc: Self::Acc) -> Self::Output;
+
+    /// Apply `f` to each element before it reaches this collector.
+    fn map_input<U, F>(self, f: F) -> MapInput<Self, F, T>
+    where Self: Sized, F: FnMut(U) -> T {
+        MapInput { collector: self, closure: f, marker: PhantomData }
+    }
+
+    /// Only pass on the elements for which `pred` holds.
+    fn filter_input<P>(self, pred: P) -> FilterInput<Self, P>
+    where Self: Sized, P: FnMut(&T) -> bool {
+        FilterInput { collector: self, predicate: pred }
+    }
+
+    /// Apply `f` to the result.
+    fn and_then<R, F>(self, f: F) -> AndThen<Self, F>
+    where Self: Sized, F: FnOnce(Self::Output) -> R {
+        AndThen { collector: self, closure: f }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct MapInput<C, F, T> {
+    collector: C,
+    closure: F,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<U, T, C: Collector<T>, F: FnMut(U) -> T> Collector<U> for MapInput<C, F, T> {
+    type Acc = C::Acc;
+    type Output = C::Output;
+
+    #[inline]
+    fn init(&mut self) -> C::Acc { self.collector.init() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut C::Acc, item: U) -> bool {
+        self.collector.accumulate(acc, (self.closure)(item))
+    }
+
+    #[inline]
+    fn finish(self, acc: C::Acc) -> C::Output { self.collector.finish(acc) }
+}
+
+#[derive(Copy, Clone)]
+pub struct FilterInput<C, P> {
+    collector: C,
+    predicate: P,
+}
+
+impl<T, C: Collector<T>, P: FnMut(&T) -> bool> Collector<T> for FilterInput<C, P> {
+    type Acc = C::Acc;
+    type Output = C::Output;
+
+    #[inline]
+    fn init(&mut self) -> C::Acc { self.collector.init() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut C::Acc, item: T) -> bool {
+        (self.predicate)(&item) && self.collector.accumulate(acc, item)
+    }
+
+    #[inline]
+    fn finish(self, acc: C::Acc) -> C::Output { self.collector.finish(acc) }
+}
+
+#[derive(Copy, Clone)]
+pub struct AndThen<C, F> {
+    collector: C,
+    closure: F,
+}
+
+impl<T, R, C: Collector<T>, F: FnOnce(C::Output) -> R> Collector<T> for AndThen<C, F> {
+    type Acc = C::Acc;
+    type Output = R;
+
+    #[inline]
+    fn init(&mut self) -> C::Acc { self.collector.init() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut C::Acc, item: T) -> bool {
+        self.collector.accumulate(acc, item)
+    }
+
+    #[inline]
+    fn finish(self, acc: C::Acc) -> R { (self.closure)(self.collector.finish(acc)) }
+}
+
+// Each collector in a tuple gets its own clone of every element, until it
+// says it has seen enough. The last one gets the element itself.
+macro_rules! tuple_collector {
+    ($($c:ident $acc:ident $done:ident),+; $lc:ident $lacc:ident $ldone:ident) => {
+        impl<T: Clone, $($c: Collector<T>,)+ $lc: Collector<T>> Collector<T>
+        for ($($c,)+ $lc) {
+            type Acc = ($(($c::Acc, bool),)+ ($lc::Acc, bool));
+            type Output = ($($c::Output,)+ $lc::Output);
+
+            #[allow(non_snake_case)]
+            fn init(&mut self) -> Self::Acc {
+                let ($(ref mut $c,)+ ref mut $lc) = *self;
+                ($(($c.init(), false),)+ ($lc.init(), false))
+            }
+
+            #[allow(non_snake_case)]
+            fn accumulate(&mut self, acc: &mut Self::Acc, item: T) -> bool {
+                let ($(ref mut $c,)+ ref mut $lc) = *self;
+                let ($((ref mut $acc, ref mut $done),)+ (ref mut $lacc, ref mut $ldone)) = *acc;
+                $(
+                    if !*$done { *$done = $c.accumulate($acc, item.clone()); }
+                )+
+                if !*$ldone { *$ldone = $lc.accumulate($lacc, item); }
+                $(*$done &&)+ *$ldone
+            }
+
+            #[allow(non_snake_case)]
+            fn finish(self, acc: Self::Acc) -> Self::Output {
+                let ($($c,)+ $lc) = self;
+                let ($(($acc, _),)+ ($lacc, _)) = acc;
+                ($($c.finish($acc),)+ $lc.finish($lacc))
+            }
+        }
+    }
+}
+
+tuple_collector!(A a_acc a_done; B b_acc b_done);
+tuple_collector!(A a_acc a_done, B b_acc b_done; C c_acc c_done);
+tuple_collector!(A a_acc a_done, B b_acc b_done, C c_acc c_done; D d_acc d_done);
+
+/// Counts the elements.
+#[derive(Copy, Clone, Debug)]
+pub struct Count;
+
+/// Creates a collector that counts the elements.
+#[inline]
+pub fn count() -> Count { Count }
+
+impl<T> Collector<T> for Count {
+    type Acc = usize;
+    type Output = usize;
+
+    #[inline]
+    fn init(&mut self) -> usize { 0 }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut usize, _: T) -> bool {
+        *acc += 1;
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: usize) -> usize { acc }
+}
+
+/// Adds up the elements.
+#[derive(Copy, Clone, Debug)]
+pub struct Sum;
+
+/// Creates a collector that adds up the elements, starting from zero.
+#[inline]
+pub fn sum() -> Sum { Sum }
+
+impl<T: Zero> Collector<T> for Sum {
+    type Acc = T;
+    type Output = T;
+
+    #[inline]
+    fn init(&mut self) -> T { T::zero() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut T, item: T) -> bool {
+        *acc = mem::replace(acc, T::zero()) + item;
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: T) -> T { acc }
+}
+
+/// Keeps the greatest element.
+#[derive(Copy, Clone, Debug)]
+pub struct Max;
+
+/// Creates a collector that keeps the greatest element, or the last of
+/// them if several are equally great.
+#[inline]
+pub fn max() -> Max { Max }
+
+impl<T: Ord> Collector<T> for Max {
+    type Acc = Option<T>;
+    type Output = Option<T>;
+
+    #[inline]
+    fn init(&mut self) -> Option<T> { None }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut Option<T>, item: T) -> bool {
+        match *acc {
+            Some(ref max) if *max > item => {},
+            _ => *acc = Some(item)
+        }
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: Option<T>) -> Option<T> { acc }
+}
+
+/// Keeps the least element.
+#[derive(Copy, Clone, Debug)]
+pub struct Min;
+
+/// Creates a collector that keeps the least element, or the first of them
+/// if several are equally small.
+#[inline]
+pub fn min() -> Min { Min }
+
+impl<T: Ord> Collector<T> for Min {
+    type Acc = Option<T>;
+    type Output = Option<T>;
+
+    #[inline]
+    fn init(&mut self) -> Option<T> { None }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut Option<T>, item: T) -> bool {
+        match *acc {
+            Some(ref min) if *min <= item => {},
+            _ => *acc = Some(item)
+        }
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: Option<T>) -> Option<T> { acc }
+}
+
+/// Keeps the first element.
+#[derive(Copy, Clone, Debug)]
+pub struct First;
+
+/// Creates a collector that keeps the first element, stopping the
+/// traversal as soon as it has it.
+#[inline]
+pub fn first() -> First { First }
+
+impl<T> Collector<T> for First {
+    type Acc = Option<T>;
+    type Output = Option<T>;
+
+    #[inline]
+    fn init(&mut self) -> Option<T> { None }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut Option<T>, item: T) -> bool {
+        *acc = Some(item);
+        true
+    }
+
+    #[inline]
+    fn finish(self, acc: Option<T>) -> Option<T> { acc }
+}
+
+/// Keeps the last element.
+#[derive(Copy, Clone, Debug)]
+pub struct Last;
+
+/// Creates a collector that keeps the last element.
+#[inline]
+pub fn last() -> Last { Last }
+
+impl<T> Collector<T> for Last {
+    type Acc = Option<T>;
+    type Output = Option<T>;
+
+    #[inline]
+    fn init(&mut self) -> Option<T> { None }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut Option<T>, item: T) -> bool {
+        *acc = Some(item);
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: Option<T>) -> Option<T> { acc }
+}
+
+/// Folds the elements into an accumulator.
+#[derive(Copy, Clone)]
+pub struct Fold<B, F> {
+    init: B,
+    closure: F,
+}
+
+/// Creates a collector that folds every element into a clone of `init`.
+///
+/// While the closure runs, another clone of `init` stands in for the
+/// accumulator, so `init` should be cheap to clone.
+#[inline]
+pub fn fold<B: Clone, F>(init: B, f: F) -> Fold<B, F> {
+    Fold { init: init, closure: f }
+}
+
+impl<T, B: Clone, F: FnMut(B, T) -> B> Collector<T> for Fold<B, F> {
+    type Acc = B;
+    type Output = B;
+
+    #[inline]
+    fn init(&mut self) -> B { self.init.clone() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut B, item: T) -> bool {
+        let b = mem::replace(acc, self.init.clone());
+        *acc = (self.closure)(b, item);
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: B) -> B { acc }
+}
+
+/// Builds a collection one element at a time.
+pub struct Extending<C> {
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C> Copy for Extending<C> {}
+impl<C> Clone for Extending<C> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Creates a collector that extends a new `C` with each element as it
+/// arrives.
+#[inline]
+pub fn extend<C>() -> Extending<C> {
+    Extending { marker: PhantomData }
+}
+
+impl<T, C: Default + Extend<T>> Collector<T> for Extending<C> {
+    type Acc = C;
+    type Output = C;
+
+    #[inline]
+    fn init(&mut self) -> C { C::default() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut C, item: T) -> bool {
+        acc.extend(Some(item));
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: C) -> C { acc }
+}
+
+/// Builds a collection through its `FromTraversal` implementation.
+pub struct Collecting<C> {
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C> Copy for Collecting<C> {}
+impl<C> Clone for Collecting<C> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Creates a collector that builds a `C` with `FromTraversal`.
+///
+/// The elements are buffered in a `Vec` until the end, since
+/// `FromTraversal` needs a whole traversal; use `extend` for collections
+/// that can grow one element at a time.
+#[inline]
+pub fn from_traversal<C>() -> Collecting<C> {
+    Collecting { marker: PhantomData }
+}
+
+impl<T, C: FromTraversal<T>> Collector<T> for Collecting<C> {
+    type Acc = Vec<T>;
+    type Output = C;
+
+    #[inline]
+    fn init(&mut self) -> Vec<T> { Vec::new() }
+
+    #[inline]
+    fn accumulate(&mut self, acc: &mut Vec<T>, item: T) -> bool {
+        acc.push(item);
+        false
+    }
+
+    #[inline]
+    fn finish(self, acc: Vec<T>) -> C { C::from_traversal(Internal::new(acc)) }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::collections::{BTreeMap, HashSet};
+    use super::*;
+    use utils::range;
+    use Traversal;
+
+    #[test]
+    fn test_builtins() {
+        assert_eq!(range(0, 5).collect_with(count()), 5);
+        assert_eq!(range(0, 5).collect_with(sum()), 10);
+        assert_eq!(range(0, 0).collect_with(sum()), 0);
+        assert_eq!(range(0, 5).collect_with(max()), Some(4));
+        assert_eq!(range(0, 5).collect_with(min()), Some(0));
+        assert_eq!(range(0, 0).collect_with(max()), None::<i32>);
+        assert_eq!(range(0, 5).collect_with(first()), Some(0));
+        assert_eq!(range(0, 5).collect_with(last()), Some(4));
+        assert_eq!(range(1, 5).collect_with(fold(1, |acc, x| acc * x)), 24);
+    }
+
+    #[test]
+    fn test_early_stop() {
+        let mut produced = 0;
+        assert_eq!(range(0, 100).inspect(|_| produced += 1).collect_with(first()), Some(0));
+        assert_eq!(produced, 1);
+
+        // A tuple only stops once all of its collectors have.
+        let mut produced = 0;
+        let both = range(0, 100).inspect(|_| produced += 1).collect_with((first(), first()));
+        assert_eq!(both, (Some(0), Some(0)));
+        assert_eq!(produced, 1);
+
+        let mut produced = 0;
+        let both = range(0, 100).inspect(|_| produced += 1).collect_with((first(), count()));
+        assert_eq!(both, (Some(0), 100));
+        assert_eq!(produced, 100);
+    }
+
+    #[test]
+    fn test_tuples() {
+        let (n, total, largest) = range(1, 5).collect_with((count(), sum(), max()));
+        assert_eq!((n, total, largest), (4, 10, Some(4)));
+        let (n, total, smallest, last_one) =
+            range(1, 5).collect_with((count(), sum(), min(), last()));
+        assert_eq!((n, total, smallest, last_one), (4, 10, Some(1), Some(4)));
+
+        // Tuples nest.
+        let ((n, total), largest) = range(1, 5).collect_with(((count(), sum()), max()));
+        assert_eq!((n, total, largest), (4, 10, Some(4)));
+        // Only the members before the last get clones.
+        #[derive(Debug)]
+        struct Counted<'a>(&'a Cell<usize>);
+        impl<'a> Clone for Counted<'a> {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Counted(self.0)
+            }
+        }
+        let clones = Cell::new(0);
+        let (n, last_one) = range(0, 5).map(|_| Counted(&clones))
+                                       .collect_with((count(), last()));
+        assert_eq!((n, clones.get()), (5, 5));
+        assert!(last_one.is_some());
+        let (_, _, _) = range(0, 5).map(|_| Counted(&clones))
+                                   .collect_with((count(), count(), count()));
+        assert_eq!(clones.get(), 15);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let evens = count().filter_input(|x: &i32| x % 2 == 0);
+        assert_eq!(range(0, 10).collect_with(evens), 5);
+
+        let lengths = sum().map_input(|s: &str| s.len());
+        assert_eq!((&["ab", "cde"][..]).cloned().collect_with(lengths), 5);
+
+        let mean = (sum(), count()).and_then(|(total, n): (i32, usize)| total as f64 / n as f64);
+        assert_eq!(range(1, 5).collect_with(mean), 2.5);
+
+        // Collectors are plain values that can be reused.
+        let odd_count = count().filter_input(|x: &i32| x % 2 == 1);
+        assert_eq!(range(0, 10).collect_with(odd_count), 5);
+        assert_eq!(range(0, 3).collect_with(odd_count), 1);
+
+        let stopping = first().filter_input(|x: &i32| *x > 3).and_then(|x| x.map(|x| x * 10));
+        assert_eq!(range(0, 10).collect_with(stopping), Some(40));
+    }
+
+    #[test]
+    fn test_collections() {
+        let (all, set): (Vec<i32>, HashSet<i32>) =
+            range(0, 6).collect_with((extend(), extend::<HashSet<_>>().map_input(|x| x % 3)));
+        assert_eq!(all, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(set.len(), 3);
+
+        let map: BTreeMap<i32, i32> =
+            range(0, 3).collect_with(from_traversal().map_input(|x| (x, x * x)));
+        assert_eq!(map.get(&2), Some(&4));
+        let (vec, n): (Vec<i32>, usize) = range(0, 3).collect_with((from_traversal(), count()));
+        assert_eq!((vec, n), (vec![0, 1, 2], 3));
+    }
+}
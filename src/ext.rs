@@ -1,75 +1,117 @@
 use super::*;
-use std::ops::Deref;
+use std::cmp;
+use std::iter;
+use std::mem;
+use std::panic;
+use std::slice;
+use std::ops::{Deref, Index};
 
 impl<I: Traversal, O, F: FnMut(I::Item) -> O>
 Traversal for Map<I, F> {
     type Item = F::Output;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(F::Output) -> bool {
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(F::Output) -> bool {
         let mut closure = self.closure;
         self.iter.foreach(move |t| {
             f(closure(t))
-        });
+        })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
 }
 
 impl<I: Traversal, F: FnMut(&I::Item) -> bool>
 Traversal for Filter<I, F> {
     type Item = I::Item;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(I::Item) -> bool{
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool{
         let mut predicate = self.predicate;
         self.iter.foreach(move |t| {
             if predicate(&t) { f(t) } else { false }
-        });
+        })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
 }
 
 impl<O, I: Traversal, F: FnMut(I::Item) -> Option<O>>
 Traversal for FilterMap<I, F> {
     type Item = O;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(O) -> bool {
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(O) -> bool {
         let mut predicate = self.predicate;
         self.iter.foreach(move |t| {
             match predicate(t) {
                 Some(o) => f(o),
                 None => false
             }
-        });
+        })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
 }
 
 impl<I: Traversal>
 Traversal for Enumerate<I> {
     type Item = (usize, I::Item);
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut((usize, I::Item)) -> bool {
-        let mut counter = 0;
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut((usize, I::Item)) -> bool {
+        // None once the next index no longer fits, so that only an element
+        // which really has no index panics.
+        let mut counter = Some(0usize);
         self.0.foreach(|t| {
-            let res = f((counter, t));
-            counter += 1;
-            res
+            let index = counter.expect("Enumerate index overflowed usize");
+            counter = index.checked_add(1);
+            f((index, t))
+        })
+    }
+
+    fn foreach_strided<F1>(self, start: usize, step: usize, mut f: F1) -> Status
+    where F1: FnMut((usize, I::Item)) -> bool {
+        let mut counter = Some(start);
+        self.0.foreach_strided(start, step, |t| {
+            let index = counter.expect("Enumerate index overflowed usize");
+            counter = index.checked_add(step);
+            f((index, t))
         })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { self.0.trusted_len() }
 }
 
 impl<I: Traversal>
 Traversal for Skip<I> {
     type Item = I::Item;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(I::Item) -> bool {
-        let mut counter = 0;
-        let n = self.n;
+    fn foreach<F1>(self, f: F1) -> Status where F1: FnMut(I::Item) -> bool {
+        self.iter.foreach_strided(self.n, 1, f)
+    }
 
-        self.iter.foreach(|t| {
-            if counter != n {
-                counter += 1;
-                false
-            } else {
-                f(t)
-            }
-        })
+    fn foreach_strided<F1>(self, start: usize, step: usize, f: F1) -> Status
+    where F1: FnMut(I::Item) -> bool {
+        match self.n.checked_add(start) {
+            Some(start) => self.iter.foreach_strided(start, step, f),
+            // Past anything that could be indexed.
+            None => walk_strided(self, start, step, f)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_sub(self.n), upper.map(|u| u.saturating_sub(self.n)))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> {
+        self.iter.trusted_len().map(|len| len.saturating_sub(self.n))
     }
 }
 
@@ -77,18 +119,40 @@ impl<I: Traversal>
 Traversal for Take<I> {
     type Item = I::Item;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(I::Item) -> bool {
-        let mut counter = 0;
-        let n = self.n;
+    fn foreach<F1>(self, f: F1) -> Status where F1: FnMut(I::Item) -> bool {
+        self.foreach_strided(0, 1, f)
+    }
 
-        self.iter.foreach(|t| {
-            if counter != n {
-                counter += 1;
-                f(t)
-            } else {
-                true
-            }
-        })
+    fn foreach_strided<F1>(self, start: usize, step: usize, mut f: F1) -> Status
+    where F1: FnMut(I::Item) -> bool {
+        // Don't start the inner traversal at all if it can't yield anything.
+        if start >= self.n { return Status::Done }
+
+        let mut remaining = (self.n - start - 1) / step + 1;
+        let mut full = false;
+
+        // Stop right after the last element rather than on the one after it,
+        // so the inner traversal never produces more than n elements.
+        let status = self.iter.foreach_strided(start, step, |t| {
+            if f(t) { return true }
+            remaining -= 1;
+            full = remaining == 0;
+            full
+        });
+
+        // Stopping the inner traversal ourselves still completes the take.
+        if full { Status::Done } else { status }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (cmp::min(lower, self.n), Some(upper.map_or(self.n, |u| cmp::min(u, self.n))))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> {
+        self.iter.trusted_len().map(|len| cmp::min(len, self.n))
     }
 }
 
@@ -96,7 +160,7 @@ impl<I: Traversal, F: FnMut(&I::Item) -> bool>
 Traversal for SkipWhile<I, F> {
     type Item = I::Item;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(I::Item) -> bool {
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
         let mut predicate = self.predicate;
         let mut flag = false;
         self.iter.foreach(move |t| {
@@ -111,19 +175,361 @@ Traversal for SkipWhile<I, F> {
             } else {
                 f(t)
             }
-        });
+        })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
 }
 
 impl<I: Traversal, F: FnMut(&I::Item) -> bool>
 Traversal for TakeWhile<I, F> {
     type Item = I::Item;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(I::Item) -> bool {
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
         let mut predicate = self.predicate;
-        self.iter.foreach(move |t| {
-            if predicate(&t) { f(t) } else { true }
+        let mut exhausted = false;
+
+        let status = self.iter.foreach(|t| {
+            if predicate(&t) {
+                f(t)
+            } else {
+                exhausted = true;
+                true
+            }
+        });
+
+        if exhausted { Status::Done } else { status }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
+}
+
+impl<I: Traversal, O, F: FnMut(I::Item) -> Option<O>>
+Traversal for MapWhile<I, F> {
+    type Item = O;
+
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(O) -> bool {
+        let mut closure = self.closure;
+        let mut exhausted = false;
+
+        let status = self.iter.foreach(|t| {
+            match closure(t) {
+                Some(o) => f(o),
+                None => {
+                    exhausted = true;
+                    true
+                }
+            }
+        });
+
+        if exhausted { Status::Done } else { status }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
+}
+
+impl<I: Traversal, St, O, F: FnMut(&mut St, I::Item) -> Option<O>>
+Traversal for Scan<I, St, F> {
+    type Item = O;
+
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(O) -> bool {
+        let mut state = self.state;
+        let mut closure = self.closure;
+        let mut exhausted = false;
+
+        let status = self.iter.foreach(|t| {
+            match closure(&mut state, t) {
+                Some(o) => f(o),
+                None => {
+                    exhausted = true;
+                    true
+                }
+            }
+        });
+
+        if exhausted { Status::Done } else { status }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.iter.size_hint().1) }
+}
+
+impl<I: Traversal, F: CoalescePredicate<I::Item>>
+Traversal for Coalesce<I, F> {
+    type Item = I::Item;
+
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
+        let mut merge = self.f;
+        let mut pending = None;
+
+        let status = self.iter.foreach(|t| {
+            match pending.take() {
+                None => {
+                    pending = Some(t);
+                    false
+                },
+                Some(p) => match merge.coalesce_pair(p, t) {
+                    Ok(merged) => {
+                        pending = Some(merged);
+                        false
+                    },
+                    Err((p, t)) => {
+                        pending = Some(t);
+                        f(p)
+                    }
+                }
+            }
+        });
+
+        // The end of a run is only known once the source finishes.
+        match pending {
+            Some(p) if status.is_done() => if f(p) { Status::Stopped } else { Status::Done },
+            _ => status
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (cmp::min(lower, 1), upper)
+    }
+}
+
+impl<T, F> CoalescePredicate<T> for F where F: FnMut(T, T) -> Result<T, (T, T)> {
+    #[inline]
+    fn coalesce_pair(&mut self, pending: T, next: T) -> Result<T, (T, T)> {
+        self(pending, next)
+    }
+}
+
+impl<T: PartialEq> CoalescePredicate<T> for DedupEq {
+    #[inline]
+    fn coalesce_pair(&mut self, pending: T, next: T) -> Result<T, (T, T)> {
+        if pending == next { Ok(pending) } else { Err((pending, next)) }
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> CoalescePredicate<T> for DedupPred<F> {
+    #[inline]
+    fn coalesce_pair(&mut self, pending: T, next: T) -> Result<T, (T, T)> {
+        if (self.0)(&pending, &next) { Ok(pending) } else { Err((pending, next)) }
+    }
+}
+
+impl<T, K: PartialEq, F: FnMut(&T) -> K> CoalescePredicate<T> for DedupKey<F> {
+    #[inline]
+    fn coalesce_pair(&mut self, pending: T, next: T) -> Result<T, (T, T)> {
+        if (self.0)(&pending) == (self.0)(&next) { Ok(pending) } else { Err((pending, next)) }
+    }
+}
+
+impl<T: PartialEq> CoalescePredicate<(usize, T)> for DedupCount {
+    #[inline]
+    fn coalesce_pair(&mut self, pending: (usize, T), next: (usize, T))
+                     -> Result<(usize, T), ((usize, T), (usize, T))> {
+        if pending.1 == next.1 {
+            Ok((pending.0 + next.0, pending.1))
+        } else {
+            Err((pending, next))
+        }
+    }
+}
+
+impl<I: Traversal, K: PartialEq, F: FnMut(&I::Item) -> K> GroupBy<I, F> {
+    /// Run the closure on each group boundary and element in turn, without
+    /// building a `Vec` per group.
+    ///
+    /// Return true from the closure to end the iteration.
+    pub fn foreach_events<F1>(self, mut f: F1) -> Status
+    where F1: for<'a> FnMut(GroupEvent<&'a K, I::Item>) -> bool {
+        let mut key = self.key;
+        let mut current: Option<K> = None;
+
+        let status = self.iter.foreach(|t| {
+            let k = key(&t);
+            if current.as_ref() != Some(&k) {
+                if current.is_some() && f(GroupEvent::End) { return true }
+                current = Some(k);
+                if let Some(ref k) = current {
+                    if f(GroupEvent::Start(k)) { return true }
+                }
+            }
+            f(GroupEvent::Elem(t))
+        });
+
+        if status.is_done() && current.is_some() && f(GroupEvent::End) {
+            return Status::Stopped
+        }
+        status
+    }
+}
+
+impl<I: Traversal, K: PartialEq, F: FnMut(&I::Item) -> K>
+Traversal for GroupBy<I, F> {
+    type Item = (K, Vec<I::Item>);
+
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut((K, Vec<I::Item>)) -> bool {
+        let mut key = self.key;
+        let mut group: Option<(K, Vec<I::Item>)> = None;
+
+        let status = self.iter.foreach(|t| {
+            let k = key(&t);
+            match group {
+                Some((ref current, ref mut elems)) if *current == k => {
+                    elems.push(t);
+                    false
+                },
+                _ => match group.replace((k, vec![t])) {
+                    Some(finished) => f(finished),
+                    None => false
+                }
+            }
+        });
+
+        match group {
+            Some(group) if status.is_done() => if f(group) { Status::Stopped } else { Status::Done },
+            _ => status
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (cmp::min(lower, 1), upper)
+    }
+}
+
+impl<I: Traversal, P: FnMut(&I::Item) -> bool> Split<I, P> {
+    /// Run the closure on each segment boundary and element in turn,
+    /// without building a `Vec` per segment.
+    ///
+    /// Return true from the closure to end the iteration.
+    pub fn foreach_events<F>(self, mut f: F) -> Status
+    where F: FnMut(GroupEvent<(), I::Item>) -> bool {
+        let mut predicate = self.predicate;
+        let mut open = false;
+
+        let status = self.iter.foreach(|t| {
+            if !open {
+                open = true;
+                if f(GroupEvent::Start(())) { return true }
+            }
+            if predicate(&t) {
+                open = false;
+                f(GroupEvent::End)
+            } else {
+                f(GroupEvent::Elem(t))
+            }
+        });
+        if status.is_stopped() { return status }
+
+        // There is always a last segment, even if it is empty.
+        if !open && f(GroupEvent::Start(())) { return Status::Stopped }
+        if f(GroupEvent::End) { Status::Stopped } else { Status::Done }
+    }
+}
+
+impl<I: Traversal, P: FnMut(&I::Item) -> bool>
+Traversal for Split<I, P> {
+    type Item = Vec<I::Item>;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(Vec<I::Item>) -> bool {
+        let mut predicate = self.predicate;
+        let mut segment = Vec::new();
+
+        let status = self.iter.foreach(|t| {
+            if predicate(&t) {
+                f(mem::take(&mut segment))
+            } else {
+                segment.push(t);
+                false
+            }
+        });
+        if status.is_stopped() { return status }
+
+        if f(segment) { Status::Stopped } else { Status::Done }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (1, upper.and_then(|u| u.checked_add(1)))
+    }
+}
+
+impl<I: Traversal> Traversal for Intersperse<I, I::Item> where I::Item: Clone {
+    type Item = I::Item;
+
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(I::Item) -> bool {
+        let separator = self.separator;
+        IntersperseWith { iter: self.iter, separator: || separator.clone() }.foreach(f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { interspersed_hint(self.iter.size_hint()) }
+}
+
+impl<I: Traversal, F: FnMut() -> I::Item>
+Traversal for IntersperseWith<I, F> {
+    type Item = I::Item;
+
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
+        let mut separator = self.separator;
+        let mut first = true;
+        self.iter.foreach(|t| {
+            if !first && f(separator()) { return true }
+            first = false;
+            f(t)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { interspersed_hint(self.iter.size_hint()) }
+}
+
+fn interspersed_hint((lower, upper): (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let with_separators = |n: usize| n.saturating_mul(2).saturating_sub(1);
+    (with_separators(lower), upper.and_then(|u| u.checked_mul(2)).map(|u| u.saturating_sub(1)))
+}
+
+impl<I: Traversal> Traversal for WithPosition<I> {
+    type Item = (Position, I::Item);
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut((Position, I::Item)) -> bool {
+        let mut pending = None;
+        let mut first = true;
+
+        let status = self.iter.foreach(|t| {
+            match pending.replace(t) {
+                Some(p) => {
+                    let position = if first { Position::First } else { Position::Middle };
+                    first = false;
+                    f((position, p))
+                },
+                None => false
+            }
         });
+
+        match pending {
+            Some(p) if status.is_done() => {
+                let position = if first { Position::Only } else { Position::Last };
+                if f((position, p)) { Status::Stopped } else { Status::Done }
+            },
+            _ => status
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a, I> fmt::Display for DisplayWith<'a, I>
+where I: Traversal + Clone, I::Item: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.iter.clone().join_to(f, self.separator)
     }
 }
 
@@ -131,47 +537,121 @@ impl<I: Traversal, F: FnMut(&I::Item)>
 Traversal for Inspect<I, F> {
     type Item = I::Item;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(I::Item) -> bool {
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
         let mut closure = self.closure;
         self.iter.foreach(move |t| {
             closure(&t);
             f(t)
-        });
+        })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
 }
 
 impl<T, I: Traversal<Item=T>, O: Traversal<Item=T>>
 Traversal for Chain<I, O> {
     type Item = T;
 
-    fn foreach<F1>(self, mut f: F1) where F1: FnMut(T) -> bool {
-        let mut flag = false;
-        self.one.foreach(|t: T| {
-            flag = f(t); flag
-        });
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(T) -> bool {
+        match self.one.foreach(&mut f) {
+            Status::Done => self.two.foreach(f),
+            Status::Stopped => Status::Stopped
+        }
+    }
 
-        if !flag {
-            self.two.foreach(|t: T| {
-                f(t)
-            });
+    fn foreach_strided<F1>(self, start: usize, step: usize, mut f: F1) -> Status
+    where F1: FnMut(T) -> bool {
+        // Jumping into the second half needs the exact length of the first,
+        // and the first must be safe to skip without running it.
+        let len = match self.one.trusted_len() {
+            Some(len) => len,
+            None => return walk_strided(self, start, step, f)
+        };
+
+        if start >= len {
+            return self.two.foreach_strided(start - len, step, f);
+        }
+
+        match self.one.foreach_strided(start, step, &mut f) {
+            Status::Done => {
+                // Where the stride lands first in the second half.
+                let over = (len - start) % step;
+                let offset = if over == 0 { 0 } else { step - over };
+                self.two.foreach_strided(offset, step, f)
+            },
+            Status::Stopped => Status::Stopped
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower_one, upper_one) = self.one.size_hint();
+        let (lower_two, upper_two) = self.two.size_hint();
+        (lower_one.saturating_add(lower_two),
+         upper_one.and_then(|one| upper_two.and_then(|two| one.checked_add(two))))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> {
+        match (self.one.trusted_len(), self.two.trusted_len()) {
+            (Some(one), Some(two)) => one.checked_add(two),
+            _ => None
         }
     }
 }
 
-impl<I: Traversal, O: Traversal, F: FnMut(I::Item) -> O>
+impl<I: Traversal, O: IntoTraversal, F: FnMut(I::Item) -> O>
 Traversal for FlatMap<I, F> {
-    type Item = <F::Output as Traversal>::Item;
+    type Item = O::Item;
 
-    fn foreach<F1>(self, mut f: F1)
+    fn foreach<F1>(self, mut f: F1) -> Status
     where F1: FnMut(Self::Item) -> bool {
         let mut producer = self.producer;
-        let mut flag = false;
         self.iter.foreach(|t| {
-            producer(t).foreach(|o| {
-                flag = f(o); flag
-            });
-            flag
-        });
+            producer(t).into_traversal().foreach(&mut f).is_stopped()
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { flattened_hint(self.iter.size_hint()) }
+}
+
+impl<I: Traversal, O: IntoIterator, F: FnMut(I::Item) -> O>
+Traversal for FlatMapIter<I, F> {
+    type Item = O::Item;
+
+    fn foreach<F1>(self, mut f: F1) -> Status
+    where F1: FnMut(Self::Item) -> bool {
+        let mut producer = self.producer;
+        self.iter.foreach(|t| {
+            producer(t).into_iter().any(&mut f)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { flattened_hint(self.iter.size_hint()) }
+}
+
+impl<I: Traversal> Traversal for Flatten<I> where I::Item: IntoTraversal {
+    type Item = <I::Item as IntoTraversal>::Item;
+
+    fn foreach<F>(self, mut f: F) -> Status
+    where F: FnMut(Self::Item) -> bool {
+        self.iter.foreach(|t| {
+            t.into_traversal().foreach(&mut f).is_stopped()
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { flattened_hint(self.iter.size_hint()) }
+}
+
+fn flattened_hint(outer: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    // Nothing is known about the inner traversals until they are produced.
+    match outer {
+        (_, Some(0)) => (0, Some(0)),
+        _ => (0, None)
     }
 }
 
@@ -181,82 +661,1834 @@ where I::Item: Deref,
       <I::Item as Deref>::Target: Clone {
     type Item = <I::Item as Deref>::Target;
 
-    fn foreach<F>(self, mut f: F) where F: FnMut(Self::Item) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(Self::Item) -> bool {
         self.iter.foreach(|d| {
             f(d.deref().clone())
-        });
+        })
+    }
+
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(Self::Item) -> bool {
+        self.iter.foreach_strided(start, step, |d| {
+            f(d.deref().clone())
+        })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { self.iter.trusted_len() }
 }
 
-#[cfg(test)]
-mod test {
-    use utils::*;
-    use Traversal;
+impl<I: Traversal>
+Traversal for StepBy<I> {
+    type Item = I::Item;
 
-    #[test]
-    fn map() {
-        let vec: Vec<_> = range(0, 5).map(|x| x * 2).collect();
-        assert_eq!(vec, &[0, 2, 4, 6, 8]);
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(I::Item) -> bool {
+        self.iter.foreach_strided(0, self.step, f)
     }
 
-    #[test]
-    fn filter() {
-        let vec: Vec<_> = range(0, 10).filter(|x| x % 2 == 0).collect();
-        assert_eq!(vec, &[0, 2, 4, 6, 8]);
+    fn foreach_strided<F>(self, start: usize, step: usize, f: F) -> Status
+    where F: FnMut(I::Item) -> bool {
+        match (start.checked_mul(self.step), step.checked_mul(self.step)) {
+            (Some(start), Some(step)) => self.iter.foreach_strided(start, step, f),
+            _ => walk_strided(self, start, step, f)
+        }
     }
 
-    #[test]
-    fn filter_map() {
-        let vec: Vec<_> = range(0, 10).filter_map(|x| if x % 2 == 0 {
-            Some(x / 2)
-        } else {
-            None
-        }).collect();
-        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let step = self.step;
+        let stepped = |n: usize| if n == 0 { 0 } else { (n - 1) / step + 1 };
+        let (lower, upper) = self.iter.size_hint();
+        (stepped(lower), upper.map(stepped))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> {
+        self.iter.trusted_len().map(|len| if len == 0 { 0 } else { (len - 1) / self.step + 1 })
+    }
+}
+
+impl<I: IndexedTraversal, O: IndexedTraversal>
+Traversal for Zip<I, O> {
+    type Item = (I::Item, O::Item);
+
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(Self::Item) -> bool {
+        self.foreach_strided(0, 1, f)
+    }
+
+    fn foreach_strided<F>(mut self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(Self::Item) -> bool {
+        let len = cmp::min(self.one.len(), self.two.len());
+        let mut index = start;
+        while index < len {
+            if f((self.one.get(index), self.two.get(index))) { return Status::Stopped }
+            index = match index.checked_add(step) { Some(i) => i, None => break };
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = cmp::min(self.one.len(), self.two.len());
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> {
+        match (self.one.trusted_len(), self.two.trusted_len()) {
+            (Some(one), Some(two)) => Some(cmp::min(one, two)),
+            _ => None
+        }
+    }
+}
+
+impl<I: Traversal, J: Iterator>
+Traversal for ZipIter<I, J> {
+    type Item = (I::Item, J::Item);
+
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(Self::Item) -> bool {
+        self.foreach_strided(0, 1, f)
+    }
+
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(Self::Item) -> bool {
+        let mut other = self.other;
+
+        // Fetch each partner ahead of its element, so the traversal is
+        // stopped right after the last pair and never started at all if
+        // `other` is empty.
+        let mut next = other.nth(start);
+        if next.is_none() { return Status::Done }
+        let mut exhausted = false;
+
+        let status = self.iter.foreach_strided(start, step, |t| {
+            let partner = next.take().expect("zip_iter: traversal ran past the iterator");
+            if f((t, partner)) { return true }
+            next = other.nth(step - 1);
+            exhausted = next.is_none();
+            exhausted
+        });
+
+        // Stopping the traversal ourselves still completes the zip.
+        if exhausted { Status::Done } else { status }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let (other_lower, other_upper) = self.other.size_hint();
+        let upper = match (upper, other_upper) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, b) => a.or(b)
+        };
+        (cmp::min(lower, other_lower), upper)
+    }
+}
+
+impl<I: Traversal, J: Iterator>
+Traversal for ZipLongestIter<I, J> {
+    type Item = EitherOrBoth<I::Item, J::Item>;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(Self::Item) -> bool {
+        let mut other = self.other;
+        let mut exhausted = false;
+
+        let status = self.iter.foreach(|t| {
+            if !exhausted {
+                if let Some(u) = other.next() { return f(EitherOrBoth::Both(t, u)) }
+                exhausted = true;
+            }
+            f(EitherOrBoth::Left(t))
+        });
+        if status.is_stopped() { return status }
+
+        if !exhausted {
+            for u in other {
+                if f(EitherOrBoth::Right(u)) { return Status::Stopped }
+            }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let (other_lower, other_upper) = self.other.size_hint();
+        let upper = match (upper, other_upper) {
+            (Some(a), Some(b)) => Some(cmp::max(a, b)),
+            _ => None
+        };
+        (cmp::max(lower, other_lower), upper)
+    }
+}
+
+impl<I: Traversal>
+Traversal for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(Vec<I::Item>) -> bool {
+        let size = self.size;
+        let mut chunk = Vec::new();
+        let status = self.iter.foreach(|t| {
+            chunk.push(t);
+            chunk.len() == size && f(mem::take(&mut chunk))
+        });
+
+        // Flush the short tail, which only exists if the source ran dry.
+        if status.is_done() && !chunk.is_empty() && f(chunk) {
+            return Status::Stopped
+        }
+        status
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let chunks = |n: usize| n.div_ceil(self.size);
+        (chunks(lower), upper.map(chunks))
+    }
+}
+
+impl<I: Traversal> ChunksExact<I> {
+    /// Run the closure on every full chunk, then return the status along
+    /// with the leftover elements that did not fill a chunk.
+    ///
+    /// The leftovers are always empty if the closure stopped the iteration.
+    pub fn foreach_with_remainder<F>(self, mut f: F) -> (Status, Vec<I::Item>)
+    where F: FnMut(Vec<I::Item>) -> bool {
+        let size = self.size;
+        let mut chunk = Vec::new();
+        let status = self.iter.foreach(|t| {
+            chunk.push(t);
+            chunk.len() == size && f(mem::take(&mut chunk))
+        });
+        (status, chunk)
+    }
+}
+
+impl<I: Traversal>
+Traversal for ChunksExact<I> {
+    type Item = Vec<I::Item>;
+
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(Vec<I::Item>) -> bool {
+        self.foreach_with_remainder(f).0
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower / self.size, upper.map(|u| u / self.size))
+    }
+}
+
+impl<I: Traversal, F: FnMut(&I::Item) -> usize>
+Traversal for BatchByWeight<I, F> {
+    type Item = Vec<I::Item>;
+
+    fn foreach<F1>(self, mut f: F1) -> Status where F1: FnMut(Vec<I::Item>) -> bool {
+        let max = self.max;
+        let mut weight = self.weight;
+        let mut batch = Vec::new();
+        let mut total = 0usize;
+        let status = self.iter.foreach(|t| {
+            let w = weight(&t);
+            let full = !batch.is_empty() && total.saturating_add(w) > max;
+            if full && f(mem::take(&mut batch)) {
+                return true
+            }
+            if full { total = 0; }
+            total = total.saturating_add(w);
+            batch.push(t);
+            false
+        });
+
+        if status.is_done() && !batch.is_empty() && f(batch) {
+            return Status::Stopped
+        }
+        status
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (cmp::min(lower, 1), upper)
+    }
+}
+
+impl<I: Traversal + Clone> Traversal for Cycle<I> {
+    type Item = I::Item;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(I::Item) -> bool {
+        loop {
+            let mut yielded = false;
+            let status = self.iter.clone().foreach(|t| {
+                yielded = true;
+                f(t)
+            });
+            if status.is_stopped() { return Status::Stopped }
+
+            // An empty pass means every pass is empty.
+            if !yielded { return Status::Done }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.iter.size_hint() {
+            (_, Some(0)) => (0, Some(0)),
+            (0, _) => (0, None),
+            _ => (usize::MAX, None)
+        }
+    }
+}
+
+impl<I: Traversal + Clone> Traversal for CycleN<I> {
+    type Item = I::Item;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(I::Item) -> bool {
+        if self.n == 0 { return Status::Done }
+
+        for _ in 1..self.n {
+            let mut yielded = false;
+            let status = self.iter.clone().foreach(|t| {
+                yielded = true;
+                f(t)
+            });
+            if status.is_stopped() { return Status::Stopped }
+            if !yielded { return Status::Done }
+        }
+
+        // The last pass can use up the original.
+        self.iter.foreach(f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_mul(self.n), upper.and_then(|u| u.checked_mul(self.n)))
+    }
+}
+
+impl<I: Traversal, W: TupleWindow<I::Item>>
+Traversal for TupleWindows<I, W> {
+    type Item = W;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(W) -> bool {
+        let size = W::size();
+        let mut first = Vec::with_capacity(size);
+        let mut window: Option<W> = None;
+        self.iter.foreach(|t| {
+            let next = match window {
+                Some(ref mut window) => {
+                    window.shift(t);
+                    window.clone()
+                },
+                None => {
+                    first.push(t);
+                    if first.len() < size { return false }
+                    let next = W::from_vec(mem::take(&mut first));
+                    window = Some(next.clone());
+                    next
+                }
+            };
+            f(next)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let overlap = W::size() - 1;
+        (lower.saturating_sub(overlap), upper.map(|u| u.saturating_sub(overlap)))
+    }
+}
+
+impl<T: Clone> TupleWindow<T> for (T, T) {
+    #[inline]
+    fn size() -> usize { 2 }
+
+    fn from_vec(elems: Vec<T>) -> Self {
+        let mut elems = elems.into_iter();
+        match (elems.next(), elems.next()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => panic!("TupleWindow: expected 2 elements")
+        }
+    }
+
+    #[inline]
+    fn shift(&mut self, next: T) {
+        self.0 = mem::replace(&mut self.1, next);
+    }
+}
+
+impl<T: Clone> TupleWindow<T> for (T, T, T) {
+    #[inline]
+    fn size() -> usize { 3 }
+
+    fn from_vec(elems: Vec<T>) -> Self {
+        let mut elems = elems.into_iter();
+        match (elems.next(), elems.next(), elems.next()) {
+            (Some(a), Some(b), Some(c)) => (a, b, c),
+            _ => panic!("TupleWindow: expected 3 elements")
+        }
+    }
+
+    #[inline]
+    fn shift(&mut self, next: T) {
+        self.0 = mem::replace(&mut self.1, mem::replace(&mut self.2, next));
+    }
+}
+
+impl<I: Traversal> Windows<I> {
+    /// Run the closure on a borrowed view of each window, without copying
+    /// the elements out.
+    ///
+    /// Return true from the closure to end the iteration.
+    pub fn foreach_view<F>(self, mut f: F) -> Status
+    where F: for<'a> FnMut(WindowView<'a, I::Item>) -> bool {
+        let size = self.size;
+        // Once full, `buffer[head]` is the oldest element of the window.
+        let mut buffer = Vec::with_capacity(size);
+        let mut head = 0;
+        self.iter.foreach(|t| {
+            if buffer.len() < size {
+                buffer.push(t);
+                if buffer.len() < size { return false }
+            } else {
+                buffer[head] = t;
+                head = if head + 1 == size { 0 } else { head + 1 };
+            }
+            let (back, front) = buffer.split_at(head);
+            f(WindowView { front: front, back: back })
+        })
+    }
+}
+
+impl<I: Traversal> Traversal for Windows<I> where I::Item: Clone {
+    type Item = Vec<I::Item>;
+
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(Vec<I::Item>) -> bool {
+        self.foreach_view(|window| f(window.to_vec()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let overlap = self.size - 1;
+        (lower.saturating_sub(overlap), upper.map(|u| u.saturating_sub(overlap)))
+    }
+}
+
+impl<'a, T> WindowView<'a, T> {
+    /// The number of elements in the window.
+    #[inline]
+    pub fn len(&self) -> usize { self.front.len() + self.back.len() }
+
+    /// Whether the window holds no elements, which never happens for the
+    /// windows of a traversal.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// The element at `index`, counting from the oldest.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.front.len() {
+            self.front.get(index)
+        } else {
+            self.back.get(index - self.front.len())
+        }
+    }
+
+    /// The two slices holding the window, oldest elements first.
+    #[inline]
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) { (self.front, self.back) }
+
+    /// Iterate over the window from oldest to newest.
+    pub fn iter(&self) -> iter::Chain<slice::Iter<'a, T>, slice::Iter<'a, T>> {
+        self.front.iter().chain(self.back.iter())
+    }
+
+    /// Copy the window into a `Vec`.
+    pub fn to_vec(&self) -> Vec<T> where T: Clone {
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend_from_slice(self.front);
+        vec.extend_from_slice(self.back);
+        vec
+    }
+}
+
+impl<'a, T> Index<usize> for WindowView<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("WindowView: index out of bounds")
+    }
+}
+
+impl<I: DoubleEndedTraversal>
+Traversal for Rev<I> {
+    type Item = I::Item;
+
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(I::Item) -> bool {
+        self.iter.foreach_rev(f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { self.iter.trusted_len() }
+}
+
+impl<I: DoubleEndedTraversal>
+DoubleEndedTraversal for Rev<I> {
+    fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(I::Item) -> bool {
+        self.iter.foreach(f)
+    }
+}
+
+impl<I: DoubleEndedTraversal, O, F: FnMut(I::Item) -> O>
+DoubleEndedTraversal for Map<I, F> {
+    fn foreach_rev<F1>(self, mut f: F1) -> Status where F1: FnMut(F::Output) -> bool {
+        let mut closure = self.closure;
+        self.iter.foreach_rev(move |t| {
+            f(closure(t))
+        })
+    }
+}
+
+impl<I: DoubleEndedTraversal, F: FnMut(&I::Item) -> bool>
+DoubleEndedTraversal for Filter<I, F> {
+    fn foreach_rev<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
+        let mut predicate = self.predicate;
+        self.iter.foreach_rev(move |t| {
+            if predicate(&t) { f(t) } else { false }
+        })
+    }
+}
+
+impl<O, I: DoubleEndedTraversal, F: FnMut(I::Item) -> Option<O>>
+DoubleEndedTraversal for FilterMap<I, F> {
+    fn foreach_rev<F1>(self, mut f: F1) -> Status where F1: FnMut(O) -> bool {
+        let mut predicate = self.predicate;
+        self.iter.foreach_rev(move |t| {
+            match predicate(t) {
+                Some(o) => f(o),
+                None => false
+            }
+        })
+    }
+}
+
+impl<I: DoubleEndedTraversal + ExactSizeTraversal>
+DoubleEndedTraversal for Enumerate<I> {
+    fn foreach_rev<F1>(self, mut f: F1) -> Status where F1: FnMut((usize, I::Item)) -> bool {
+        // Count down from the length so elements keep their forward indices.
+        let mut counter = self.0.len();
+        self.0.foreach_rev(|t| {
+            counter -= 1;
+            f((counter, t))
+        })
+    }
+}
+
+impl<I: DoubleEndedTraversal, F: FnMut(&I::Item)>
+DoubleEndedTraversal for Inspect<I, F> {
+    fn foreach_rev<F1>(self, mut f: F1) -> Status where F1: FnMut(I::Item) -> bool {
+        let mut closure = self.closure;
+        self.iter.foreach_rev(move |t| {
+            closure(&t);
+            f(t)
+        })
+    }
+}
+
+impl<T, I: DoubleEndedTraversal<Item=T>, O: DoubleEndedTraversal<Item=T>>
+DoubleEndedTraversal for Chain<I, O> {
+    fn foreach_rev<F1>(self, mut f: F1) -> Status where F1: FnMut(T) -> bool {
+        match self.two.foreach_rev(&mut f) {
+            Status::Done => self.one.foreach_rev(f),
+            Status::Stopped => Status::Stopped
+        }
+    }
+}
+
+impl<I: DoubleEndedTraversal>
+DoubleEndedTraversal for Cloned<I>
+where I::Item: Deref,
+      <I::Item as Deref>::Target: Clone {
+    fn foreach_rev<F>(self, mut f: F) -> Status where F: FnMut(Self::Item) -> bool {
+        self.iter.foreach_rev(|d| {
+            f(d.deref().clone())
+        })
+    }
+}
+
+impl<I: ExactSizeTraversal + DoubleEndedTraversal> ExactSizeTraversal for Rev<I> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for StepBy<I> {}
+impl<I: IndexedTraversal, O: IndexedTraversal> ExactSizeTraversal for Zip<I, O> {}
+impl<I: ExactSizeTraversal, J: ExactSizeIterator> ExactSizeTraversal for ZipIter<I, J> {}
+impl<I: ExactSizeTraversal, W: TupleWindow<I::Item>> ExactSizeTraversal for TupleWindows<I, W> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for Chunks<I> {}
+impl<I: ExactSizeTraversal + Clone> ExactSizeTraversal for CycleN<I> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len().checked_mul(self.n).expect("cycle_n: length overflows a usize")
+    }
+}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for WithPosition<I> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for ChunksExact<I> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for Windows<I> where I::Item: Clone {}
+impl<I: ExactSizeTraversal, O, F: FnMut(I::Item) -> O> ExactSizeTraversal for Map<I, F> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for Enumerate<I> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for Skip<I> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for Take<I> {}
+impl<I: ExactSizeTraversal, F: FnMut(&I::Item)> ExactSizeTraversal for Inspect<I, F> {}
+impl<I: ExactSizeTraversal> ExactSizeTraversal for Cloned<I>
+where I::Item: Deref,
+      <I::Item as Deref>::Target: Clone {}
+
+impl<I: IndexedTraversal, O, F: FnMut(I::Item) -> O>
+IndexedTraversal for Map<I, F> {
+    #[inline]
+    fn get(&mut self, index: usize) -> O { (self.closure)(self.iter.get(index)) }
+}
+
+impl<I: IndexedTraversal>
+IndexedTraversal for Enumerate<I> {
+    #[inline]
+    fn get(&mut self, index: usize) -> (usize, I::Item) { (index, self.0.get(index)) }
+}
+
+impl<I: IndexedTraversal>
+IndexedTraversal for Skip<I> {
+    #[inline]
+    fn get(&mut self, index: usize) -> I::Item { self.iter.get(index + self.n) }
+}
+
+impl<I: IndexedTraversal>
+IndexedTraversal for Take<I> {
+    #[inline]
+    fn get(&mut self, index: usize) -> I::Item { self.iter.get(index) }
+}
+
+impl<I: IndexedTraversal>
+IndexedTraversal for StepBy<I> {
+    #[inline]
+    fn get(&mut self, index: usize) -> I::Item { self.iter.get(index * self.step) }
+}
+
+impl<I: IndexedTraversal + DoubleEndedTraversal>
+IndexedTraversal for Rev<I> {
+    #[inline]
+    fn get(&mut self, index: usize) -> I::Item {
+        let len = self.iter.len();
+        self.iter.get(len - 1 - index)
+    }
+}
+
+impl<I: IndexedTraversal>
+IndexedTraversal for Cloned<I>
+where I::Item: Deref,
+      <I::Item as Deref>::Target: Clone {
+    #[inline]
+    fn get(&mut self, index: usize) -> Self::Item { self.iter.get(index).deref().clone() }
+}
+
+impl<I: IndexedTraversal, O: IndexedTraversal>
+IndexedTraversal for Zip<I, O> {
+    #[inline]
+    fn get(&mut self, index: usize) -> Self::Item {
+        (self.one.get(index), self.two.get(index))
+    }
+}
+
+impl<T> Iterator for SpawnIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self.rx.recv() {
+            Ok(t) => {
+                self.hint.0 = self.hint.0.saturating_sub(1);
+                self.hint.1 = self.hint.1.map(|upper| upper.saturating_sub(1));
+                Some(t)
+            },
+            Err(_) => {
+                self.hint = (0, Some(0));
+                if let Some(producer) = self.producer.take() {
+                    if let Err(payload) = producer.join() {
+                        panic::resume_unwind(payload)
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.hint }
+}
+
+impl<T> iter::FusedIterator for SpawnIter<T> {}
+
+impl<T> Drop for SpawnIter<T> {
+    fn drop(&mut self) {
+        // Hang up first so a producer blocked on a full channel wakes up and
+        // stops, then wait for it instead of leaving it detached.
+        let (_, hung_up) = mpsc::sync_channel(0);
+        drop(mem::replace(&mut self.rx, hung_up));
+        if let Some(producer) = self.producer.take() {
+            if let Err(payload) = producer.join() {
+                if !thread::panicking() {
+                    panic::resume_unwind(payload)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeSet, HashSet};
+    use std::ops::ControlFlow;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use utils::*;
+    use {Traversal, ExactSizeTraversal, IndexedTraversal, IntoTraversal, Internal, Status};
+
+    #[test]
+    fn map() {
+        let vec: Vec<_> = range(0, 5).map(|x| x * 2).collect();
+        assert_eq!(vec, &[0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn filter() {
+        let vec: Vec<_> = range(0, 10).filter(|x| x % 2 == 0).collect();
+        assert_eq!(vec, &[0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn filter_map() {
+        let vec: Vec<_> = range(0, 10).filter_map(|x| if x % 2 == 0 {
+            Some(x / 2)
+        } else {
+            None
+        }).collect();
+        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn enumerate() {
+        let vec: Vec<_> = range(1, 5).enumerate().collect();
+        assert_eq!(vec, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn take() {
+        let vec: Vec<_> = range(0, 10).take(5).collect();
+        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn skip() {
+        let vec: Vec<_> = range(0, 10).skip(5).collect();
+        assert_eq!(vec, &[5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn take_evaluations() {
+        let mut seen = 0;
+        let vec: Vec<_> = count(0, 1).inspect(|_| seen += 1).take(5).collect();
+        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+        assert_eq!(seen, 5);
+
+        seen = 0;
+        assert_eq!(range(0, 3).inspect(|_| seen += 1).take(5).count(), 3);
+        assert_eq!(seen, 3);
+
+        let mut calls = 0;
+        let vec: Vec<_> = iterate(1, |x| { calls += 1; x * 2 }).take(4).collect();
+        assert_eq!(vec, &[1, 2, 4, 8]);
+        assert_eq!(calls, 3);
+
+        let mut pulled = 0;
+        let vec: Vec<_> = Internal::new((0..).inspect(|_| pulled += 1)).take(2).collect();
+        assert_eq!(vec, &[0, 1]);
+        assert_eq!(pulled, 2);
+    }
+
+    #[test]
+    fn take_zero() {
+        let mut seen = 0;
+        assert_eq!(count(0, 1).inspect(|_| seen += 1).take(0).count(), 0);
+        assert_eq!(seen, 0);
+
+        let mut calls = 0;
+        assert_eq!(iterate(1, |x| { calls += 1; x }).take(0).foreach(|_| true), Status::Done);
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn take_with_take_while() {
+        let mut seen = 0;
+        let vec: Vec<_> = count(0, 1).inspect(|_| seen += 1)
+                                     .take_while(|&x| x < 10).take(3).collect();
+        assert_eq!(vec, &[0, 1, 2]);
+        assert_eq!(seen, 3);
+
+        seen = 0;
+        let vec: Vec<_> = count(0, 1).inspect(|_| seen += 1)
+                                     .take(10).take_while(|&x| x < 3).collect();
+        assert_eq!(vec, &[0, 1, 2]);
+        // take_while has to see the first failing element.
+        assert_eq!(seen, 4);
+
+        seen = 0;
+        let vec: Vec<_> = count(0, 1).inspect(|_| seen += 1).take(3).take(3).collect();
+        assert_eq!(vec, &[0, 1, 2]);
+        assert_eq!(seen, 3);
+    }
+
+    #[test]
+    fn take_while() {
+        let vec: Vec<_> = range(0, 10).take_while(|&x| x < 5).collect();
+        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn skip_while() {
+        let vec: Vec<_> = range(0, 10).skip_while(|&x| x < 5).collect();
+        assert_eq!(vec, &[5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn inspect() {
+        let mut x = 0;
+        let vec: Vec<_> = range(0, 5).inspect(|&y| x += y).collect();
+        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+        assert_eq!(x, 10);
+    }
+
+    #[test]
+    fn chain() {
+        let vec: Vec<_> = range(5, 10).chain(range(0, 5)).collect();
+        assert_eq!(vec, &[5, 6, 7, 8, 9, 0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn find() {
+        assert_eq!(range(0, 10).find(|&x| x * x > 10), Some(4));
+        assert_eq!(range(0, 10).find(|&x| x > 10), None);
+        assert_eq!(range(0, 10).chain(range(20, 30)).find(|&x| x > 15), Some(20));
+        assert_eq!(range(0, 5).flat_map(|x| range(0, x)).find(|&x| x == 3), Some(3));
+    }
+
+    #[test]
+    fn find_map() {
+        assert_eq!(range(1, 10).find_map(|x| if x % 4 == 0 { Some(x / 4) } else { None }),
+                   Some(1));
+        assert_eq!(range(1, 3).find_map(|x| if x > 5 { Some(x) } else { None }), None);
+    }
+
+    #[test]
+    fn position() {
+        assert_eq!(range(5, 10).position(|x| x == 7), Some(2));
+        assert_eq!(range(5, 10).skip(1).position(|x| x == 7), Some(1));
+        assert_eq!(range(5, 10).position(|x| x == 11), None);
+    }
+
+    #[test]
+    fn rposition() {
+        assert_eq!(range(0, 10).rposition(|x| x % 3 == 0), Some(9));
+        assert_eq!(range(0, 10).rposition(|x| x == 4), Some(4));
+        assert_eq!(range(0, 10).rposition(|x| x > 10), None);
+    }
+
+    #[test]
+    fn any_all() {
+        assert!(range(0, 10).any(|x| x == 9));
+        assert!(!range(0, 10).any(|x| x == 10));
+        assert!(!range(0, 0).any(|_| true));
+        assert!(range(0, 10).all(|x| x < 10));
+        assert!(!range(0, 10).all(|x| x < 9));
+        assert!(range(0, 0).all(|_| false));
+    }
+
+    #[test]
+    fn nth_last() {
+        assert_eq!(range(0, 10).map(|x| x * 2).nth(3), Some(6));
+        assert_eq!(range(0, 10).nth(10), None);
+        assert_eq!(range(0, 10).filter(|x| x % 2 == 1).last(), Some(9));
+        assert_eq!(range(0, 0).last(), None);
+    }
+
+    #[test]
+    fn short_circuit() {
+        let mut seen = 0;
+        assert_eq!(iterate(1, |x| x * 2).inspect(|_| seen += 1).find(|&x| x > 100), Some(128));
+        assert_eq!(seen, 8);
+
+        seen = 0;
+        assert_eq!(range(0, 100).inspect(|_| seen += 1).position(|x| x == 3), Some(3));
+        assert_eq!(seen, 4);
+
+        seen = 0;
+        assert!(repeat(1).inspect(|_| seen += 1).any(|x| x == 1));
+        assert_eq!(seen, 1);
+
+        seen = 0;
+        assert!(!count(0, 1).inspect(|_| seen += 1).all(|x| x < 5));
+        assert_eq!(seen, 6);
+
+        seen = 0;
+        assert_eq!(range(0, 3).chain(range(10, 20)).inspect(|_| seen += 1).nth(4), Some(11));
+        assert_eq!(seen, 5);
+    }
+
+    #[test]
+    fn try_fold() {
+        let sum: Option<i32> = range(0, 5).try_fold(0, |acc, x| Some(acc + x));
+        assert_eq!(sum, Some(10));
+
+        let overflow = range(250u8, 255).try_fold(0u8, |acc, x| acc.checked_add(x));
+        assert_eq!(overflow, None);
+
+        let flow = range(0, 10).try_fold(0, |acc, x| {
+            if x == 4 { ControlFlow::Break(acc) } else { ControlFlow::Continue(acc + x) }
+        });
+        assert_eq!(flow, ControlFlow::Break(6));
+    }
+
+    #[test]
+    fn try_for_each() {
+        fn parse(rows: &[&'static str]) -> Result<Vec<i32>, String> {
+            let mut out = vec![];
+            rows.cloned()
+                .map(|row| row.parse::<i32>().map_err(|_| row.to_string()))
+                .try_for_each(|row| row.map(|x| out.push(x)))?;
+            Ok(out)
+        }
+
+        assert_eq!(parse(&["1", "2", "3"]), Ok(vec![1, 2, 3]));
+        assert_eq!(parse(&["1", "x", "3", "y"]), Err("x".to_string()));
     }
 
     #[test]
-    fn enumerate() {
-        let vec: Vec<_> = range(1, 5).enumerate().collect();
-        assert_eq!(vec, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    fn try_for_each_propagates() {
+        let mut seen = 0;
+        let res = range(0, 3)
+            .flat_map(|x| range(0, x))
+            .chain(range(10, 20))
+            .filter(|x| x % 2 == 0)
+            .inspect(|_| seen += 1)
+            .try_for_each(|x| if x > 10 { Err(x) } else { Ok(()) });
+        assert_eq!(res, Err(12));
+        assert_eq!(seen, 4);
     }
 
     #[test]
-    fn take() {
-        let vec: Vec<_> = range(0, 10).take(5).collect();
-        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+    fn fold_reduce() {
+        assert_eq!(range(1, 5).fold(1, |acc, x| acc * x), 24);
+        assert_eq!(range(0, 0).fold(7, |acc, x| acc + x), 7);
+        assert_eq!(range(1, 5).reduce(|a, b| a * 10 + b), Some(1234));
+        assert_eq!(range(0, 0).reduce(|a, b| a + b), None);
     }
 
     #[test]
-    fn skip() {
-        let vec: Vec<_> = range(0, 10).skip(5).collect();
-        assert_eq!(vec, &[5, 6, 7, 8, 9]);
+    fn status() {
+        assert_eq!(range(0, 5).map(|x| x * 2).foreach(|_| false), Status::Done);
+        assert_eq!(range(0, 5).filter(|&x| x > 2).foreach(|x| x == 3), Status::Stopped);
+        assert_eq!(range(0, 5).skip(10).foreach(|_| true), Status::Done);
+        assert_eq!(count(0, 1).take(3).foreach(|_| false), Status::Done);
+        assert_eq!(count(0, 1).take(3).foreach(|x| x == 2), Status::Stopped);
+        assert_eq!(count(0, 1).take_while(|&x| x < 3).foreach(|_| false), Status::Done);
+        assert_eq!(count(0, 1).take_while(|&x| x < 3).foreach(|x| x == 2), Status::Stopped);
     }
 
     #[test]
-    fn take_while() {
-        let vec: Vec<_> = range(0, 10).take_while(|&x| x < 5).collect();
-        assert_eq!(vec, &[0, 1, 2, 3, 4]);
+    fn status_nested() {
+        let mut seen = vec![];
+        let status = range(0, 3).chain(range(10, 13)).foreach(|x| { seen.push(x); x == 1 });
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(seen, &[0, 1]);
+
+        seen.clear();
+        let status = range(0, 4).flat_map(|x| range(0, x)).foreach(|x| { seen.push(x); x == 1 });
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(seen, &[0, 0, 1]);
+
+        let status = range(0, 4).flat_map(|x| range(0, x)).chain(range(0, 2)).foreach(|_| false);
+        assert_eq!(status, Status::Done);
     }
 
     #[test]
-    fn skip_while() {
-        let vec: Vec<_> = range(0, 10).skip_while(|&x| x < 5).collect();
-        assert_eq!(vec, &[5, 6, 7, 8, 9]);
+    fn size_hint() {
+        assert_eq!(range(0, 10).map(|x| x * 2).size_hint(), (10, Some(10)));
+        assert_eq!(range(0, 10).filter(|&x| x > 2).size_hint(), (0, Some(10)));
+        assert_eq!(range(0, 10).filter_map(Some).size_hint(), (0, Some(10)));
+        assert_eq!(range(0, 10).enumerate().size_hint(), (10, Some(10)));
+        assert_eq!(range(0, 10).skip(3).size_hint(), (7, Some(7)));
+        assert_eq!(range(0, 10).skip(30).size_hint(), (0, Some(0)));
+        assert_eq!(range(0, 10).take(3).size_hint(), (3, Some(3)));
+        assert_eq!(range(0, 2).take(3).size_hint(), (2, Some(2)));
+        assert_eq!(count(0, 1).take(3).size_hint(), (3, Some(3)));
+        assert_eq!(count(0, 1).skip(3).size_hint(), (usize::MAX - 3, None));
+        assert_eq!(range(0, 10).take_while(|&x| x < 3).size_hint(), (0, Some(10)));
+        assert_eq!(range(0, 10).skip_while(|&x| x < 3).size_hint(), (0, Some(10)));
+        assert_eq!(range(0, 10).inspect(|_| ()).size_hint(), (10, Some(10)));
+        assert_eq!(range(0, 10).chain(range(0, 5)).size_hint(), (15, Some(15)));
+        assert_eq!(range(0, 10).chain(count(0, 1)).size_hint(), (usize::MAX, None));
+        assert_eq!(range(0, 10).flat_map(|x| range(0, x)).size_hint(), (0, None));
+        assert_eq!(range(0, 0).flat_map(|x| range(0, x)).size_hint(), (0, Some(0)));
+        assert_eq!(repeat(&1).cloned().take(4).size_hint(), (4, Some(4)));
     }
 
     #[test]
-    fn inspect() {
-        let mut x = 0;
-        let vec: Vec<_> = range(0, 5).inspect(|&y| x += y).collect();
+    fn len() {
+        assert_eq!(range(0u32, 10).map(|x| x * 2).enumerate().skip(2).take(5).len(), 5);
+        assert_eq!(range(0u8, 3).inspect(|_| ()).len(), 3);
+        assert!(range(0u8, 3).skip(3).is_empty());
+    }
+
+    #[test]
+    fn rev() {
+        let vec: Vec<_> = range(0, 5).rev().collect();
+        assert_eq!(vec, &[4, 3, 2, 1, 0]);
+        let vec: Vec<_> = range(0, 5).rev().rev().collect();
         assert_eq!(vec, &[0, 1, 2, 3, 4]);
-        assert_eq!(x, 10);
+        assert_eq!(range(0, 5).rev().size_hint(), (5, Some(5)));
     }
 
     #[test]
-    fn chain() {
-        let vec: Vec<_> = range(5, 10).chain(range(0, 5)).collect();
-        assert_eq!(vec, &[5, 6, 7, 8, 9, 0, 1, 2, 3, 4]);
+    fn rev_adapters() {
+        let vec: Vec<_> = range(0, 10).map(|x| x * 2).filter(|x| x % 3 == 0).rev().collect();
+        assert_eq!(vec, &[18, 12, 6, 0]);
+
+        let vec: Vec<_> = range(0, 6).filter_map(|x| if x % 2 == 0 { Some(x / 2) } else { None })
+                                     .rev().collect();
+        assert_eq!(vec, &[2, 1, 0]);
+
+        let mut seen = vec![];
+        let vec: Vec<_> = range(0, 3).inspect(|&x| seen.push(x)).rev().collect();
+        assert_eq!(vec, &[2, 1, 0]);
+        assert_eq!(seen, &[2, 1, 0]);
+
+        let vec: Vec<_> = range(5u32, 8).enumerate().rev().collect();
+        assert_eq!(vec, &[(2, 7), (1, 6), (0, 5)]);
+
+        let vec: Vec<_> = range(0, 3).chain(range(10, 12)).rev().collect();
+        assert_eq!(vec, &[11, 10, 2, 1, 0]);
+
+        let data = [1, 2, 3];
+        let vec: Vec<_> = data[..].cloned().rev().collect();
+        assert_eq!(vec, &[3, 2, 1]);
+    }
+
+    #[test]
+    fn rev_short_circuit() {
+        let mut seen = 0;
+        let status = range(0, 3).chain(range(10, 13)).inspect(|_| seen += 1)
+                                .rev().foreach(|x| x == 11);
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(seen, 2);
+        assert_eq!(range(0, 100).rev().find(|x| x % 7 == 0), Some(98));
+    }
+
+    #[test]
+    fn step_by() {
+        let vec: Vec<_> = range(0, 10).step_by(3).collect();
+        assert_eq!(vec, &[0, 3, 6, 9]);
+        let vec: Vec<_> = count(0, 1).filter(|x| x % 2 == 0).step_by(2).take(3).collect();
+        assert_eq!(vec, &[0, 4, 8]);
+        assert_eq!(range(0, 10).step_by(3).size_hint(), (4, Some(4)));
+        assert_eq!(range(0, 9).step_by(3).size_hint(), (3, Some(3)));
+        assert_eq!(range(0u8, 10).step_by(3).step_by(2).collect::<Vec<_>>(), &[0, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must not be zero")]
+    fn step_by_zero() {
+        range(0, 10).step_by(0);
+    }
+
+    #[test]
+    fn strided_adapters() {
+        let vec: Vec<_> = range(0, 10).map(|x| x * 10).skip(7).collect();
+        assert_eq!(vec, &[70, 80, 90]);
+        let vec: Vec<_> = range(0, 10).enumerate().skip(2).step_by(3).collect();
+        assert_eq!(vec, &[(2, 2), (5, 5), (8, 8)]);
+        let vec: Vec<_> = range(0, 10).take(7).skip(2).step_by(2).collect();
+        assert_eq!(vec, &[2, 4, 6]);
+        let vec: Vec<_> = range(0, 10).skip(2).take(7).step_by(2).collect();
+        assert_eq!(vec, &[2, 4, 6, 8]);
+        let vec: Vec<_> = range(0, 4).chain(range(10, 14)).step_by(3).collect();
+        assert_eq!(vec, &[0, 3, 12]);
+        let vec: Vec<_> = range(0, 4).chain(range(10, 14)).skip(5).collect();
+        assert_eq!(vec, &[11, 12, 13]);
+        let vec: Vec<_> = range(0, 4).filter(|_| true).chain(range(10, 14)).skip(5).collect();
+        assert_eq!(vec, &[11, 12, 13]);
+        let vec: Vec<_> = range(0, 4).skip_while(|&x| x < 1).step_by(2).collect();
+        assert_eq!(vec, &[1, 3]);
+        assert_eq!(range(0, 5).chain(range(5, 10)).nth(7), Some(7));
+        assert_eq!(range(0, 5).rev().nth(1), Some(3));
+    }
+
+    #[test]
+    fn strided_jumps() {
+        // Map runs its closure on every element skipped over, like std.
+        let mut mapped = 0;
+        assert_eq!(range(0, 10).map(|x| { mapped += 1; x }).nth(4), Some(4));
+        assert_eq!(mapped, 5);
+        let mut mapped = 0;
+        assert_eq!(range(0, 10).map(|x| { mapped += 1; x }).skip(2).step_by(3).count(), 3);
+        assert_eq!(mapped, 10);
+
+        // Jumping straight to an element of a Map is explicit, through get.
+        let mut mapped = 0;
+        let mut big = range(0u32, u32::MAX).map(|x| { mapped += 1; x * 2 });
+        assert_eq!(big.get(1 << 30), 1 << 31);
+        assert_eq!(mapped, 1);
+
+        assert_eq!(range(0u64, u64::MAX).skip(1 << 62).step_by(1 << 61).count(), 6);
+        assert_eq!(range_step(0i64, i64::MAX, 3).nth(1 << 60), Some(3 << 60));
+
+        // Inspect keeps seeing every element.
+        let mut seen = 0;
+        assert_eq!(range(0, 10).inspect(|_| seen += 1).nth(4), Some(4));
+        assert_eq!(seen, 5);
+
+        // Even when the whole first half of a chain is skipped.
+        let mut seen = 0;
+        let vec: Vec<_> = range(0, 3).inspect(|_| seen += 1).chain(range(10, 13))
+                                     .skip(4).collect();
+        assert_eq!(vec, &[11, 12]);
+        assert_eq!(seen, 3);
+        assert_eq!(range(0, 3).skip(1).chain(range(10, 13)).trusted_len(), Some(5));
+        assert_eq!(range(0, 3).inspect(|_| ()).chain(range(10, 13)).trusted_len(), None);
+    }
+
+    #[test]
+    fn zip() {
+        let vec: Vec<_> = range(0u8, 5).zip(range(10u8, 13)).collect();
+        assert_eq!(vec, &[(0, 10), (1, 11), (2, 12)]);
+        let vec: Vec<_> = range(0u32, 100).map(|x| x * 2).zip(range(0u32, 100).rev())
+                                          .skip(97).collect();
+        assert_eq!(vec, &[(194, 2), (196, 1), (198, 0)]);
+        assert_eq!(range(0u8, 5).zip(range(0u8, 5).step_by(2)).len(), 3);
+        let mut seen = 0;
+        assert_eq!(range(0u8, 5).zip(range(0u8, 5)).map(|(a, b)| { seen += 1; a + b })
+                                .find(|&x| x == 4), Some(4));
+        assert_eq!(seen, 3);
+    }
+
+    #[test]
+    fn coalesce() {
+        let vec: Vec<_> = Internal::new(vec![1, 2, -3, 4, -5, -6])
+            .coalesce(|a, b| if (a < 0) == (b < 0) { Ok(a + b) } else { Err((a, b)) })
+            .collect();
+        assert_eq!(vec, &[3, -3, 4, -11]);
+        assert_eq!(range(0, 0).coalesce(|a, b| Err((a, b))).count(), 0);
+        assert_eq!(range(0, 5).coalesce(|a, b| Ok(a + b)).collect::<Vec<_>>(), &[10]);
+        assert_eq!(range(0, 5).coalesce(|a, b| Err((a, b))).size_hint(), (1, Some(5)));
+
+        // Stopping on the flushed element is still reported.
+        assert_eq!(range(0, 5).coalesce(|a, b| Ok(a + b)).foreach(|_| true), Status::Stopped);
+    }
+
+    #[test]
+    fn dedup() {
+        let vec: Vec<_> = Internal::new(vec![1, 1, 2, 3, 3, 3, 1]).dedup().collect();
+        assert_eq!(vec, &[1, 2, 3, 1]);
+
+        // Runs that span the seams of a Chain or FlatMap.
+        let vec: Vec<_> = Internal::new(vec![1, 1, 2]).chain(Internal::new(vec![2, 2, 3, 3]))
+                                                      .dedup().collect();
+        assert_eq!(vec, &[1, 2, 3]);
+        let vec: Vec<_> = range(0, 4).flat_map(|x| Internal::new(vec![x / 2; 2])).dedup()
+                                     .collect();
+        assert_eq!(vec, &[0, 1]);
+
+        let mut produced = 0;
+        assert_eq!(count(0, 1).inspect(|_| produced += 1).map(|x| x / 3).dedup().nth(1),
+                   Some(1));
+        assert_eq!(produced, 7);
+    }
+
+    #[test]
+    fn dedup_by() {
+        // Each element is compared with the first of the run, not its neighbour.
+        let vec: Vec<_> = Internal::new(vec![1, 2, 4, 5, 9, 10, 11])
+            .dedup_by(|&a, &b| b - a == 1).collect();
+        assert_eq!(vec, &[1, 4, 9, 11]);
+        let vec: Vec<_> = Internal::new(vec!["apple", "avocado", "banana", "blueberry", "cherry"])
+            .dedup_by_key(|s| s.as_bytes()[0]).collect();
+        assert_eq!(vec, &["apple", "banana", "cherry"]);
+    }
+
+    #[test]
+    fn dedup_with_count() {
+        let vec: Vec<_> = Internal::new("aaabccdd".chars()).chain(Internal::new("d".chars()))
+                                                           .dedup_with_count().collect();
+        assert_eq!(vec, &[(3, 'a'), (1, 'b'), (2, 'c'), (3, 'd')]);
+        assert_eq!(range(0, 0).dedup_with_count().count(), 0);
+    }
+
+    #[test]
+    fn group_by() {
+        let vec: Vec<_> = Internal::new(vec![1, 3, 2, 4, 5, 6, 8]).group_by(|x| x % 2).collect();
+        assert_eq!(vec, &[(1, vec![1, 3]), (0, vec![2, 4]), (1, vec![5]), (0, vec![6, 8])]);
+        assert_eq!(range(0, 0).group_by(|&x| x).count(), 0);
+        assert_eq!(range(0, 10).group_by(|&x| x / 4).count(), 3);
+
+        // Groups spanning the seam of a Chain.
+        let vec: Vec<_> = range(0, 3).chain(range(3, 6)).group_by(|&x| x / 2)
+                                     .map(|(k, g)| (k, g.len())).collect();
+        assert_eq!(vec, &[(0, 2), (1, 2), (2, 2)]);
+
+        assert_eq!(count(0, 1).group_by(|&x| x / 3).nth(2), Some((2, vec![6, 7, 8])));
+        assert_eq!(range(0, 4).group_by(|&x| x / 2).foreach(|(k, _)| k == 1), Status::Stopped);
+    }
+
+    #[test]
+    fn group_by_events() {
+        use GroupEvent::*;
+        let mut events = Vec::new();
+        let status = Internal::new("aabc".chars()).group_by(|&c| c).foreach_events(|e| {
+            events.push(match e { Start(&k) => Start(k), Elem(t) => Elem(t), End => End });
+            false
+        });
+        assert_eq!(status, Status::Done);
+        assert_eq!(events, &[Start('a'), Elem('a'), Elem('a'), End,
+                             Start('b'), Elem('b'), End,
+                             Start('c'), Elem('c'), End]);
+
+        let mut lengths = Vec::new();
+        let mut len = 0;
+        range(0, 7).group_by(|&x| x / 3).foreach_events(|e| {
+            match e {
+                Start(_) => len = 0,
+                Elem(_) => len += 1,
+                End => lengths.push(len)
+            }
+            false
+        });
+        assert_eq!(lengths, &[3, 3, 1]);
+
+        assert_eq!(range(0, 0).group_by(|&x| x).foreach_events(|_| true), Status::Done);
+        assert_eq!(range(0, 3).group_by(|&x| x).foreach_events(|e| e == End), Status::Stopped);
+    }
+
+    #[test]
+    fn split() {
+        let vec: Vec<_> = Internal::new(vec![1, 2, 0, 3, 0, 0, 4]).split(|&x| x == 0).collect();
+        assert_eq!(vec, &[vec![1, 2], vec![3], vec![], vec![4]]);
+        let vec: Vec<_> = Internal::new(vec![0, 1, 0]).split(|&x| x == 0).collect();
+        assert_eq!(vec, &[vec![], vec![1], vec![]]);
+        let vec: Vec<Vec<i32>> = range(0, 0).split(|&x| x == 0).collect();
+        assert_eq!(vec, &[vec![]]);
+        assert_eq!(range(0, 5).split(|&x| x == 0).size_hint(), (1, Some(6)));
+
+        // Matches str::split.
+        let text = "a,,bc,";
+        let vec: Vec<String> = Internal::new(text.chars()).split(|&c| c == ',')
+                                                         .map(|s| s.into_iter().collect())
+                                                         .collect();
+        assert_eq!(vec, text.split(',').collect::<Vec<_>>());
+
+        assert_eq!(count(1, 1).split(|&x| x % 4 == 0).nth(1), Some(vec![5, 6, 7]));
+        assert_eq!(range(0, 3).split(|_| false).foreach(|_| true), Status::Stopped);
+    }
+
+    #[test]
+    fn split_events() {
+        use GroupEvent::*;
+        let mut events = Vec::new();
+        let status = Internal::new(vec![1, 0, 0]).split(|&x| x == 0).foreach_events(|e| {
+            events.push(e);
+            false
+        });
+        assert_eq!(status, Status::Done);
+        assert_eq!(events, &[Start(()), Elem(1), End, Start(()), End, Start(()), End]);
+
+        let mut events = Vec::new();
+        range(0, 0).split(|_| true).foreach_events(|e| { events.push(e); false });
+        assert_eq!(events, &[Start(()), End]);
+
+        let mut ends = 0;
+        let status = count(0, 1).split(|&x| x % 2 == 0).foreach_events(|e| {
+            if e == End { ends += 1; }
+            ends == 3
+        });
+        assert_eq!(status, Status::Stopped);
+    }
+
+    #[test]
+    fn intersperse() {
+        let vec: Vec<_> = range(1, 4).intersperse(0).collect();
+        assert_eq!(vec, &[1, 0, 2, 0, 3]);
+        assert_eq!(range(0, 0).intersperse(0).count(), 0);
+        assert_eq!(range(0, 1).intersperse(9).collect::<Vec<_>>(), &[0]);
+        assert_eq!(range(0, 3).intersperse(0).size_hint(), (5, Some(5)));
+        assert_eq!(range(0, 0).intersperse(0).size_hint(), (0, Some(0)));
+
+        let mut separators = 0;
+        let vec: Vec<_> = range(1, 4).intersperse_with(|| { separators += 1; -1 }).collect();
+        assert_eq!(vec, &[1, -1, 2, -1, 3]);
+        assert_eq!(separators, 2);
+
+        // Stopping on a separator doesn't yield the element after it.
+        let mut seen = Vec::new();
+        let status = count(0, 1).intersperse(-1).foreach(|x| { seen.push(x); x == -1 });
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(seen, &[0, -1]);
+    }
+
+    #[test]
+    fn with_position() {
+        use Position::*;
+        let vec: Vec<_> = range(0, 4).with_position().collect();
+        assert_eq!(vec, &[(First, 0), (Middle, 1), (Middle, 2), (Last, 3)]);
+        let vec: Vec<_> = range(0, 2).with_position().collect();
+        assert_eq!(vec, &[(First, 0), (Last, 1)]);
+        let vec: Vec<_> = range(0, 1).with_position().collect();
+        assert_eq!(vec, &[(Only, 0)]);
+        assert_eq!(range(0, 0).with_position().count(), 0);
+        assert_eq!(range(0, 5).with_position().len(), 5);
+        assert_eq!(count(0, 1).with_position().nth(2), Some((Middle, 2)));
+        assert_eq!(range(0, 2).with_position().foreach(|(p, _)| p == Last), Status::Stopped);
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(range(1, 4).join(", "), "1, 2, 3");
+        assert_eq!(range(0, 0).join(", "), "");
+        assert_eq!(Internal::new(vec!["a", "b"]).join(""), "ab");
+
+        let mut out = String::from("> ");
+        range(0, 3).map(|x| x * 2).join_to(&mut out, "-").unwrap();
+        assert_eq!(out, "> 0-2-4");
+
+        assert_eq!(format!("[{}]", range(1, 4).display_with(", ")), "[1, 2, 3]");
+        assert_eq!(format!("{}", linspace(0.0, 1.0, 3).display_with(" ")), "0 0.5 1");
+        assert_eq!(range(0, 3).display_with("|").to_string(), "0|1|2");
+    }
+
+    #[test]
+    fn join_to_stops_on_error() {
+        use std::fmt;
+
+        struct Limited(usize);
+        impl fmt::Write for Limited {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                if s.len() > self.0 { return Err(fmt::Error) }
+                self.0 -= s.len();
+                Ok(())
+            }
+        }
+
+        let mut produced = 0;
+        let result = count(0, 1).inspect(|_| produced += 1).join_to(&mut Limited(5), ",");
+        assert!(result.is_err());
+        assert_eq!(produced, 4);
+    }
+
+    #[test]
+    fn cycle() {
+        let workers = ["a", "b", "c"];
+        let vec: Vec<_> = (&workers[..]).cycle().take(7).collect();
+        assert_eq!(vec, &[&"a", &"b", &"c", &"a", &"b", &"c", &"a"]);
+        let vec: Vec<_> = range(0, 3).map(|x| x * 2).cycle().skip(2).take(3).collect();
+        assert_eq!(vec, &[4, 0, 2]);
+
+        // An empty pass ends the cycle instead of spinning.
+        assert_eq!(range(0, 0).cycle().foreach(|_| false), Status::Done);
+        assert_eq!(range(0, 5).filter(|&x| x > 10).cycle().foreach(|_| true), Status::Done);
+
+        assert_eq!(range(0, 3).cycle().size_hint(), (usize::MAX, None));
+        assert_eq!(range(0, 0).cycle().size_hint(), (0, Some(0)));
+        assert_eq!(range(0, 3).filter(|_| true).cycle().size_hint(), (0, None));
+    }
+
+    #[test]
+    fn cycle_n() {
+        let vec: Vec<_> = range(0, 3).cycle_n(2).collect();
+        assert_eq!(vec, &[0, 1, 2, 0, 1, 2]);
+        assert_eq!(range(0, 3).cycle_n(0).count(), 0);
+        assert_eq!(range(0, 3).cycle_n(1).count(), 3);
+        assert_eq!(range(0, 0).cycle_n(usize::MAX).foreach(|_| false), Status::Done);
+        assert_eq!(range(0, 3).cycle_n(4).len(), 12);
+        assert_eq!(range(0, 3).cycle_n(4).foreach(|x| x == 2), Status::Stopped);
+        assert_eq!(range(0, 3).cycle_n(2).last(), Some(2));
+        assert_eq!(range(0, 0).cycle_n(usize::MAX).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn cycle_n_len_overflow() {
+        range(0, 3).cycle_n(usize::MAX).len();
+    }
+
+    #[test]
+    fn flat_map_into_traversal() {
+        let vec: Vec<_> = range(0, 4).flat_map(|x| vec![x; x as usize]).collect();
+        assert_eq!(vec, &[1, 2, 2, 3, 3, 3]);
+        let vec: Vec<_> = range(0, 6).flat_map(|x| if x % 2 == 0 { Some(x) } else { None })
+                                     .collect();
+        assert_eq!(vec, &[0, 2, 4]);
+        assert_eq!(range(0, 0).flat_map(|x| vec![x]).size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn flat_map_iter() {
+        let vec: Vec<_> = range(0, 3).flat_map_iter(|x| 0..x).collect();
+        assert_eq!(vec, &[0, 0, 1]);
+        let vec: Vec<_> = Internal::new(vec!["ab", "", "c"]).flat_map_iter(|s| s.chars())
+                                                            .collect();
+        assert_eq!(vec, &['a', 'b', 'c']);
+
+        // Stopping inside an inner iterator stops the outer traversal.
+        let mut produced = 0;
+        let status = count(0, 1).inspect(|_| produced += 1).flat_map_iter(|x| 0..x)
+                                .foreach(|x| x == 1);
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(produced, 3);
+    }
+
+    #[test]
+    fn flatten() {
+        let nested = vec![vec![1, 2], vec![], vec![3]];
+        let vec: Vec<_> = (&nested).flatten().cloned().collect();
+        assert_eq!(vec, &[1, 2, 3]);
+        let vec: Vec<_> = nested.into_traversal().flatten().collect();
+        assert_eq!(vec, &[1, 2, 3]);
+
+        let vec: Vec<_> = Internal::new(vec![Some(1), None, Some(3)]).flatten().collect();
+        assert_eq!(vec, &[1, 3]);
+        let parsed: Vec<i32> = Internal::new(vec!["1", "x", "3"]).map(|s| s.parse::<i32>())
+                                                                .flatten().collect();
+        assert_eq!(parsed, &[1, 3]);
+
+        assert_eq!(range(0, 3).map(|x| range(0, x)).flatten().count(), 3);
+        assert_eq!(range(0, 5).map(|x| range(0, x)).flatten().foreach(|x| x == 2),
+                   Status::Stopped);
+    }
+
+    #[test]
+    fn partition() {
+        let (evens, odds): (Vec<_>, Vec<_>) = range(0, 7).partition(|x| x % 2 == 0);
+        assert_eq!(evens, &[0, 2, 4, 6]);
+        assert_eq!(odds, &[1, 3, 5]);
+        let (small, big): (HashSet<_>, HashSet<_>) = range(0, 0).partition(|&x| x < 3);
+        assert!(small.is_empty() && big.is_empty());
+    }
+
+    #[test]
+    fn unzip() {
+        let (xs, ys): (Vec<_>, Vec<_>) = range(0, 3).map(|x| (x, x * x)).unzip();
+        assert_eq!(xs, &[0, 1, 2]);
+        assert_eq!(ys, &[0, 1, 4]);
+        let (names, lengths): (Vec<_>, BTreeSet<_>) =
+            Internal::new(vec!["ab", "c", "de"]).map(|s| (s, s.len())).unzip();
+        assert_eq!(names, &["ab", "c", "de"]);
+        assert_eq!(lengths.into_iter().collect::<Vec<_>>(), &[1, 2]);
+    }
+
+    #[test]
+    fn sum_product() {
+        assert_eq!(range(1, 5).sum(), 10);
+        assert_eq!(range(1, 5).product(), 24);
+        assert_eq!(range(0, 0).sum(), 0);
+        assert_eq!(range(0, 0).product(), 1);
+        assert_eq!(linspace(0.0, 1.0, 5).sum(), 2.5);
+    }
+
+    #[test]
+    fn checked_sum() {
+        assert_eq!(range(0u64, 1000).checked_sum(), Some(499500));
+        assert_eq!(range(0u8, 0).checked_sum(), Some(0));
+
+        // Overflow is reported and stops the traversal straight away.
+        let mut produced = 0;
+        let total = count(u64::MAX - 3, 1).inspect(|_| produced += 1).checked_sum();
+        assert_eq!(total, None);
+        assert_eq!(produced, 2);
+
+        assert_eq!(range_inclusive(1u32, 12).checked_product(), Some(479001600));
+        assert_eq!(range_inclusive(1u32, 13).checked_product(), None);
+        assert_eq!(range(-3i8, 0).checked_product(), Some(-6));
+    }
+
+    #[test]
+    fn saturating_sum() {
+        assert_eq!(range(250u8, 255).saturating_sum(), 255);
+        assert_eq!(range(0u8, 5).saturating_sum(), 10);
+        assert_eq!(Internal::new(vec![-100i8, -100, 50]).saturating_sum(), -78);
+    }
+
+    #[test]
+    fn compensated_sum() {
+        let naive = Internal::new(vec![1.0, 1e100, 1.0, -1e100]).sum();
+        assert_eq!(naive, 0.0);
+        assert_eq!(Internal::new(vec![1.0, 1e100, 1.0, -1e100]).compensated_sum(), 2.0);
+
+        let tenths = || repeat(0.1f32).take(100000);
+        let exact = 0.1f32 as f64 * 100000.0;
+        assert!((tenths().compensated_sum() as f64 - exact).abs() < 1e-2);
+        assert!((tenths().sum() as f64 - exact).abs() > 1.0);
+        assert_eq!(range(0, 0).map(|x| x as f64).compensated_sum(), 0.0);
+    }
+
+    #[test]
+    fn min_max() {
+        let data = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        assert_eq!(Internal::new(data.clone()).max(), Some(9));
+        assert_eq!(Internal::new(data.clone()).min(), Some(1));
+        assert_eq!(range(0, 0).max(), None);
+        assert_eq!(range(0, 0).min(), None);
+
+        // Ties go to the last maximum and the first minimum, as in std.
+        let pairs = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        assert_eq!(Internal::new(pairs.clone()).max_by_key(|p| p.0), Some((1, 'c')));
+        assert_eq!(Internal::new(pairs.clone()).min_by_key(|p| p.0), Some((0, 'b')));
+        assert_eq!(Internal::new(pairs.clone()).max_by(|a, b| a.0.cmp(&b.0)), Some((1, 'c')));
+        assert_eq!(Internal::new(pairs).min_by(|a, b| a.0.cmp(&b.0)), Some((0, 'b')));
+
+        let floats = vec![2.5, -1.0, 7.25, 0.0];
+        assert_eq!(Internal::new(floats.clone()).max_by(f64::total_cmp), Some(7.25));
+        assert_eq!(Internal::new(floats).min_by(|a: &f64, b| a.total_cmp(b)), Some(-1.0));
+
+        let mut keys = 0;
+        assert_eq!(range(0, 10).max_by_key(|&x| { keys += 1; x % 4 }), Some(7));
+        assert_eq!(keys, 10);
+    }
+
+    #[test]
+    fn minmax() {
+        use MinMaxResult::*;
+        assert_eq!(range(0, 0).minmax(), NoElements);
+        assert_eq!(range(3, 4).minmax(), OneElement(3));
+        assert_eq!(Internal::new(vec![3, 1, 4, 1, 5, 9, 2, 6]).minmax(), MinMax(1, 9));
+        assert_eq!(range(3, 4).minmax().into_option(), Some((3, 3)));
+        assert_eq!(range(0, 0).minmax().into_option(), None);
+
+        let pairs = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        assert_eq!(Internal::new(pairs).minmax_by(|a, b| a.0.cmp(&b.0)),
+                   MinMax((0, 'b'), (1, 'c')));
+        assert_eq!(Internal::new(vec![2.0, -3.5, 1.0]).minmax_by(f64::total_cmp),
+                   MinMax(-3.5, 2.0));
+        assert_eq!(range(0, 2).rev().minmax(), MinMax(0, 1));
+    }
+
+    #[test]
+    fn k_smallest() {
+        let data = vec![5, 1, 8, 3, 9, 2, 7];
+        assert_eq!(Internal::new(data.clone()).k_smallest(3).collect::<Vec<_>>(), &[1, 2, 3]);
+        assert_eq!(Internal::new(data.clone()).k_largest(3).collect::<Vec<_>>(), &[9, 8, 7]);
+        assert_eq!(Internal::new(data.clone()).k_smallest(10).count(), 7);
+        assert_eq!(Internal::new(data).k_largest(0).count(), 0);
+        assert_eq!(range(0, 0).k_smallest(2).count(), 0);
+        assert_eq!(range(0, 100000).map(|x| (x * 7919) % 100000).k_largest(2)
+                                   .collect::<Vec<_>>(), &[99999, 99998]);
+
+        // Asking for everything holds only what there is.
+        let data = vec![5, 1, 8, 3, 9, 2, 7];
+        assert_eq!(Internal::new(data.clone()).k_smallest(usize::MAX).collect::<Vec<_>>(),
+                   &[1, 2, 3, 5, 7, 8, 9]);
+        assert_eq!(Internal::new(data).k_largest(usize::MAX).collect::<Vec<_>>(),
+                   &[9, 8, 7, 5, 3, 2, 1]);
+        assert_eq!(range(0, 10).filter(|_| true).k_largest(usize::MAX / 16).count(), 10);
+    }
+
+    #[test]
+    fn sorted() {
+        assert_eq!(Internal::new(vec![3, 1, 2]).sorted().collect::<Vec<_>>(), &[1, 2, 3]);
+        let words = vec!["ccc", "a", "bb", "d"];
+        assert_eq!(Internal::new(words.clone()).sorted_by_key(|s| s.len()).collect::<Vec<_>>(),
+                   &["a", "d", "bb", "ccc"]);
+        assert_eq!(Internal::new(words).sorted_by(|a, b| b.cmp(a)).collect::<Vec<_>>(),
+                   &["d", "ccc", "bb", "a"]);
+        assert_eq!(Internal::new(vec![2, 1]).sorted().len(), 2);
+    }
+
+    #[test]
+    fn map_while() {
+        let vec: Vec<_> = range(0, 10).map_while(|x| if x < 3 { Some(x * 2) } else { None })
+                                      .collect();
+        assert_eq!(vec, &[0, 2, 4]);
+        assert_eq!(count(0, 1).map_while(|x| if x < 5 { Some(x) } else { None })
+                              .foreach(|_| false), Status::Done);
+        assert_eq!(range(0, 10).map_while(Some).foreach(|x| x == 3), Status::Stopped);
+        assert_eq!(range(0, 10).map_while(Some).size_hint(), (0, Some(10)));
+    }
+
+    #[test]
+    fn scan() {
+        let sums: Vec<_> = range(1, 6).scan(0, |total, x| { *total += x; Some(*total) })
+                                      .collect();
+        assert_eq!(sums, &[1, 3, 6, 10, 15]);
+
+        // Ending the scan early with None.
+        let vec: Vec<_> = count(1, 1).scan(1, |product, x| {
+            *product *= x;
+            if *product > 100 { None } else { Some(*product) }
+        }).collect();
+        assert_eq!(vec, &[1, 2, 6, 24]);
+
+        let mut produced = 0;
+        let status = count(0, 1).inspect(|_| produced += 1)
+                                .scan((), |_, x| if x < 3 { Some(x) } else { None })
+                                .foreach(|_| false);
+        assert_eq!(status, Status::Done);
+        assert_eq!(produced, 4);
+        assert_eq!(range(0, 5).scan(0, |_, x| Some(x)).foreach(|x| x == 1), Status::Stopped);
+    }
+
+    #[test]
+    fn chunks() {
+        let vec: Vec<_> = range(0, 7).chunks(3).collect();
+        assert_eq!(vec, &[vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+        let vec: Vec<_> = range(0, 6).chunks(3).collect();
+        assert_eq!(vec, &[vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(range(0, 0).chunks(3).count(), 0);
+        assert_eq!(range(0, 7).chunks(3).len(), 3);
+        assert_eq!(range(0, 6).chunks(3).len(), 2);
+
+        // Stopping on a full chunk doesn't flush anything afterwards.
+        let mut seen = Vec::new();
+        let status = range(0, 7).chunks(3).foreach(|c| { seen.push(c); seen.len() == 2 });
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(seen.len(), 2);
+
+        // Stopping on the flushed tail is still reported.
+        assert_eq!(range(0, 7).chunks(3).foreach(|c| c.len() == 1), Status::Stopped);
+        assert_eq!(count(0, 1).chunks(2).nth(2), Some(vec![4, 5]));
+    }
+
+    #[test]
+    #[should_panic(expected = "size must not be zero")]
+    fn chunks_zero() {
+        range(0, 10).chunks(0);
+    }
+
+    #[test]
+    fn chunks_exact() {
+        let vec: Vec<_> = range(0, 7).chunks_exact(3).collect();
+        assert_eq!(vec, &[vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(range(0, 7).chunks_exact(3).len(), 2);
+
+        let mut seen = Vec::new();
+        let (status, rest) = range(0, 7).chunks_exact(3).foreach_with_remainder(|c| {
+            seen.push(c);
+            false
+        });
+        assert_eq!(status, Status::Done);
+        assert_eq!(seen.len(), 2);
+        assert_eq!(rest, &[6]);
+
+        let (status, rest) = range(0, 7).chunks_exact(3).foreach_with_remainder(|_| true);
+        assert_eq!(status, Status::Stopped);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn batch_by_weight() {
+        let words = ["a", "bb", "ccc", "dddd", "e", "ff"];
+        let vec: Vec<Vec<&str>> = Internal::new(words.iter().cloned())
+            .batch_by_weight(4, |w| w.len()).collect();
+        assert_eq!(vec, &[vec!["a", "bb"], vec!["ccc"], vec!["dddd"], vec!["e", "ff"]]);
+
+        // Oversized elements get a batch of their own.
+        let vec: Vec<_> = Internal::new(vec![1, 9, 1, 1]).batch_by_weight(3, |&x| x).collect();
+        assert_eq!(vec, &[vec![1], vec![9], vec![1, 1]]);
+
+        assert_eq!(range(0, 0).batch_by_weight(3, |_| 1).count(), 0);
+        assert_eq!(range(0, 5).batch_by_weight(0, |_| 1).count(), 5);
+        assert_eq!(range(0, 5).batch_by_weight(3, |_| 1).size_hint(), (1, Some(5)));
+        assert_eq!(range(0, 5).batch_by_weight(2, |_| 1).foreach(|b| b.len() == 1),
+                   Status::Stopped);
+        assert_eq!(count(0usize, 1).batch_by_weight(10, |&x| x).nth(1), Some(vec![5]));
+    }
+
+    #[test]
+    fn pairwise() {
+        let deltas: Vec<_> = Internal::new(vec![1, 4, 9, 16]).pairwise().map(|(a, b)| b - a)
+                                                             .collect();
+        assert_eq!(deltas, &[3, 5, 7]);
+        assert_eq!(range(0, 1).pairwise().count(), 0);
+        assert_eq!(range(0, 0).pairwise().count(), 0);
+        assert_eq!(range(0, 10).pairwise().len(), 9);
+        assert_eq!(range(0, 1).pairwise().size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn tuple_windows() {
+        let vec: Vec<(i32, i32, i32)> = range(0, 5).tuple_windows().collect();
+        assert_eq!(vec, &[(0, 1, 2), (1, 2, 3), (2, 3, 4)]);
+        assert_eq!(range(0, 2).tuple_windows::<(i32, i32, i32)>().count(), 0);
+        assert_eq!(range(0, 5).tuple_windows::<(i32, i32, i32)>().len(), 3);
+
+        let mut produced = 0;
+        let found = count(0, 1).inspect(|_| produced += 1).tuple_windows()
+                               .find(|&(a, b, c)| a + b + c > 10);
+        assert_eq!(found, Some((3, 4, 5)));
+        assert_eq!(produced, 6);
+    }
+
+    #[test]
+    fn windows() {
+        let vec: Vec<_> = range(0, 5).windows(3).collect();
+        assert_eq!(vec, &[vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]);
+        let vec: Vec<_> = range(0, 3).windows(1).collect();
+        assert_eq!(vec, &[vec![0], vec![1], vec![2]]);
+        assert_eq!(range(0, 2).windows(3).count(), 0);
+        assert_eq!(range(0, 3).windows(3).count(), 1);
+        assert_eq!(range(0, 10).windows(4).len(), 7);
+
+        let mut sums = Vec::new();
+        let status = count(0, 1).windows(3).foreach_view(|w| {
+            assert_eq!(w.len(), 3);
+            assert_eq!(w[2], w[0] + 2);
+            sums.push(w.iter().sum::<i32>());
+            w.get(0) == Some(&4)
+        });
+        assert_eq!(status, Status::Stopped);
+        assert_eq!(sums, &[3, 6, 9, 12, 15]);
+    }
+
+    #[test]
+    #[should_panic(expected = "size must not be zero")]
+    fn windows_zero() {
+        range(0, 10).windows(0);
+    }
+
+    #[test]
+    fn zip_iter() {
+        let labels = ["a", "b", "c"];
+        let vec: Vec<_> = range(0, 3).flat_map(|x| range(0, x)).zip_iter(labels.iter()).collect();
+        assert_eq!(vec, &[(0, &"a"), (0, &"b"), (1, &"c")]);
+        assert_eq!(range(0, 10).zip_iter(0..3).size_hint(), (3, Some(3)));
+        assert_eq!(range(0, 10).zip_iter(0..3).len(), 3);
+        assert_eq!(count(0, 1).zip_iter(0..).size_hint(), (usize::MAX, None));
+        let vec: Vec<_> = range(0, 10).zip_iter(20..).skip(3).step_by(3).collect();
+        assert_eq!(vec, &[(3, 23), (6, 26), (9, 29)]);
+    }
+
+    #[test]
+    fn zip_iter_stops_producer() {
+        let mut produced = 0;
+        let status = count(0, 1).inspect(|_| produced += 1).zip_iter(0..3).foreach(|_| false);
+        assert_eq!(status, Status::Done);
+        assert_eq!(produced, 3);
+
+        let mut produced = 0;
+        let status = count(0, 1).inspect(|_| produced += 1).zip_iter(None::<u8>)
+                                .foreach(|_| false);
+        assert_eq!(status, Status::Done);
+        assert_eq!(produced, 0);
+
+        assert_eq!(range(0, 2).zip_iter(0..5).foreach(|_| false), Status::Done);
+        assert_eq!(range(0, 5).zip_iter(0..5).foreach(|(x, _)| x == 4), Status::Stopped);
+    }
+
+    #[test]
+    fn zip_longest_iter() {
+        use EitherOrBoth::*;
+        let vec: Vec<_> = range(0, 3).zip_longest_iter("ab".chars()).collect();
+        assert_eq!(vec, &[Both(0, 'a'), Both(1, 'b'), Left(2)]);
+        let vec: Vec<_> = range(0, 1).zip_longest_iter("ab".chars()).collect();
+        assert_eq!(vec, &[Both(0, 'a'), Right('b')]);
+        assert_eq!(range(0, 1).zip_longest_iter(0..4).size_hint(), (4, Some(4)));
+        let first_right = count(0, 1).take(2).zip_longest_iter(0..)
+                                     .find(|e| matches!(*e, Right(_)));
+        assert_eq!(first_right, Some(Right(2)));
+        assert_eq!(range(0, 3).zip_longest_iter(0..1).foreach(|e| e == Left(2)),
+                   Status::Stopped);
+    }
+
+    #[test]
+    fn spawn_iter() {
+        let vec: Vec<_> = range(0, 100).spawn_iter().collect();
+        assert_eq!(vec, (0..100).collect::<Vec<_>>());
+        let vec: Vec<_> = count(0u32, 1).spawn_iter_bounded(0).take(3).collect();
+        assert_eq!(vec, &[0, 1, 2]);
+        let zipped: Vec<_> = repeat('a').spawn_iter().zip(0..2).collect();
+        assert_eq!(zipped, &[('a', 0), ('a', 1)]);
+
+        let mut iter = range(0, 3).spawn_iter_bounded(1);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    struct Reporting(mpsc::Sender<Status>);
+
+    impl Traversal for Reporting {
+        type Item = usize;
+
+        fn foreach<F>(self, f: F) -> Status where F: FnMut(usize) -> bool {
+            let status = count(0, 1).foreach(f);
+            self.0.send(status).unwrap();
+            status
+        }
+    }
+
+    #[test]
+    fn spawn_iter_drop_stops_producer() {
+        let (tx, rx) = mpsc::channel();
+        let mut iter = Reporting(tx).spawn_iter_bounded(2);
+        assert_eq!(iter.next(), Some(0));
+        drop(iter);
+        // The producer has already been joined, so its report is waiting.
+        assert_eq!(rx.try_recv(), Ok(Status::Stopped));
+    }
+
+    struct Slow(Arc<AtomicBool>);
+
+    impl Traversal for Slow {
+        type Item = usize;
+
+        fn foreach<F>(self, mut f: F) -> Status where F: FnMut(usize) -> bool {
+            if f(0) { return Status::Stopped }
+            thread::sleep(Duration::from_millis(50));
+            self.0.store(true, Ordering::SeqCst);
+            if f(1) { return Status::Stopped }
+            Status::Done
+        }
+    }
+
+    #[test]
+    fn spawn_iter_drop_waits_for_next_element() {
+        let reached = Arc::new(AtomicBool::new(false));
+        let mut iter = Slow(reached.clone()).spawn_iter_bounded(0);
+        assert_eq!(iter.next(), Some(0));
+        // Dropping can't return until the producer offers its next element.
+        drop(iter);
+        assert!(reached.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "late failure")]
+    fn spawn_iter_drop_panic() {
+        let iter = range(0, 10).inspect(|&x| if x == 1 { panic!("late failure") })
+                               .spawn_iter_bounded(0);
+        assert_eq!(iter.take(1).count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "producer failed")]
+    fn spawn_iter_panic() {
+        let iter = range(0, 10).inspect(|&x| if x == 5 { panic!("producer failed") })
+                               .spawn_iter();
+        assert_eq!(iter.count(), 5);
     }
 
     #[test]
@@ -0,0 +1,49 @@
+use std::ops::ControlFlow;
+use Try;
+
+impl<T, E> Try for Result<T, E> {
+    type Output = T;
+    type Residual = E;
+
+    #[inline]
+    fn from_output(output: T) -> Self { Ok(output) }
+
+    #[inline]
+    fn from_residual(residual: E) -> Self { Err(residual) }
+
+    #[inline]
+    fn branch(self) -> Result<T, E> { self }
+}
+
+impl<T> Try for Option<T> {
+    type Output = T;
+    type Residual = ();
+
+    #[inline]
+    fn from_output(output: T) -> Self { Some(output) }
+
+    #[inline]
+    fn from_residual(_: ()) -> Self { None }
+
+    #[inline]
+    fn branch(self) -> Result<T, ()> { self.ok_or(()) }
+}
+
+impl<B, C> Try for ControlFlow<B, C> {
+    type Output = C;
+    type Residual = B;
+
+    #[inline]
+    fn from_output(output: C) -> Self { ControlFlow::Continue(output) }
+
+    #[inline]
+    fn from_residual(residual: B) -> Self { ControlFlow::Break(residual) }
+
+    #[inline]
+    fn branch(self) -> Result<C, B> {
+        match self {
+            ControlFlow::Continue(c) => Ok(c),
+            ControlFlow::Break(b) => Err(b)
+        }
+    }
+}
@@ -2,9 +2,53 @@ use std::collections::*;
 use std::hash::Hash;
 use super::*;
 
+mod flow;
+mod option;
 mod slice;
+mod step;
 mod vec;
 
+impl<'a, T> Traversal for &'a VecDeque<T> {
+	type Item = &'a T;
+
+	#[inline]
+	fn foreach<F>(self, f: F) -> Status where F: FnMut(&'a T) -> bool {
+		Internal::new(self.iter()).foreach(f)
+	}
+
+	#[inline]
+	fn foreach_strided<F>(self, start: usize, step: usize, f: F) -> Status
+	where F: FnMut(&'a T) -> bool {
+		Internal::new(self.iter()).foreach_strided(start, step, f)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = VecDeque::len(self);
+		(len, Some(len))
+	}
+
+	#[inline]
+	fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<'a, T> DoubleEndedTraversal for &'a VecDeque<T> {
+	#[inline]
+	fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(&'a T) -> bool {
+		Internal::new(self.iter()).foreach_rev(f)
+	}
+}
+
+impl<T> ExactSizeTraversal for &VecDeque<T> {}
+
+impl<'a, T> IndexedTraversal for &'a VecDeque<T> {
+	#[inline]
+	fn get(&mut self, index: usize) -> &'a T {
+		let deque: &'a VecDeque<T> = self;
+		&deque[index]
+	}
+}
+
 impl<T> FromTraversal<T> for VecDeque<T> {
 	fn from_traversal<I: IntoTraversal<Item=T>>(traversable: I) -> Self {
 		let trav = traversable.into_traversal();
@@ -48,3 +92,105 @@ impl<K: Ord, V> FromTraversal<(K, V)> for BTreeMap<K, V> {
 		new
 	}
 }
+
+impl<T: Hash + Eq> FromTraversal<T> for HashSet<T> {
+	fn from_traversal<I: IntoTraversal<Item=T>>(traversable: I) -> Self {
+		let trav = traversable.into_traversal();
+		let mut new = Self::with_capacity(trav.size_hint().0);
+		trav.run(|elem| {
+			new.insert(elem);
+		});
+		new
+	}
+}
+
+impl<T: Ord> FromTraversal<T> for BTreeSet<T> {
+	fn from_traversal<I: IntoTraversal<Item=T>>(traversable: I) -> Self {
+		let trav = traversable.into_traversal();
+		let mut new = Self::new();
+		trav.run(|elem| {
+			new.insert(elem);
+		});
+		new
+	}
+}
+
+impl<T: Ord> FromTraversal<T> for BinaryHeap<T> {
+	fn from_traversal<I: IntoTraversal<Item=T>>(traversable: I) -> Self {
+		let trav = traversable.into_traversal();
+		let mut new = Self::with_capacity(trav.size_hint().0);
+		trav.run(|elem| {
+			new.push(elem);
+		});
+		new
+	}
+}
+
+/// Collects the two halves of each pair into their own collections, in a
+/// single pass.
+///
+/// This copies through a temporary. `Extend` has no stable way to reserve
+/// space, so both halves are first gathered into vectors sized from
+/// `size_hint`, and each is then moved into its collection with a single
+/// `extend` that can reserve exactly. Until then the whole input is held
+/// in those vectors as well as in whatever the collections allocate.
+impl<A, B, FromA, FromB> FromTraversal<(A, B)> for (FromA, FromB)
+where FromA: Default + Extend<A>, FromB: Default + Extend<B> {
+	fn from_traversal<I: IntoTraversal<Item=(A, B)>>(traversable: I) -> Self {
+		let trav = traversable.into_traversal();
+		let lower = trav.size_hint().0;
+		let (mut left, mut right) = (Vec::with_capacity(lower), Vec::with_capacity(lower));
+		trav.run(|(a, b)| {
+			left.push(a);
+			right.push(b);
+		});
+		let mut new = (FromA::default(), FromB::default());
+		new.0.extend(left);
+		new.1.extend(right);
+		new
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::collections::{VecDeque, HashSet, BTreeSet, BinaryHeap};
+	use utils::range;
+	use {Traversal, IndexedTraversal};
+
+	#[test]
+	fn test_vec_deque() {
+		let mut data: VecDeque<u32> = VecDeque::new();
+		data.push_back(3);
+		data.push_back(4);
+		data.push_front(2);
+		data.push_front(1);
+		assert_eq!((&data).cloned().collect::<Vec<u32>>(), vec![1, 2, 3, 4]);
+		assert_eq!((&data).skip(2).cloned().collect::<Vec<u32>>(), vec![3, 4]);
+		assert_eq!((&data).rev().nth(1), Some(&3));
+		assert_eq!((&data).zip(&[7, 8][..]).collect::<Vec<_>>(), vec![(&1, &7), (&2, &8)]);
+	}
+
+	#[test]
+	fn test_sets() {
+		let set: HashSet<u32> = range(0, 10).map(|x| x % 3).collect();
+		assert_eq!(set.len(), 3);
+		let set: BTreeSet<u32> = range(0, 10).map(|x| x % 3).collect();
+		assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+		let heap: BinaryHeap<u32> = range(0, 10).collect();
+		assert_eq!(heap.peek(), Some(&9));
+	}
+
+	#[test]
+	fn test_tuples() {
+		let (evens, odds): (Vec<_>, HashSet<_>) = range(0u32, 6).map(|x| (x * 2, x * 2 + 1))
+		                                                        .collect();
+		assert_eq!(evens, vec![0, 2, 4, 6, 8, 10]);
+		assert!(odds.contains(&11) && odds.len() == 6);
+		let (empty, also): (VecDeque<u8>, String) = range(0u8, 0).map(|x| (x, 'a')).collect();
+		assert!(empty.is_empty() && also.is_empty());
+
+		// Each side is reserved once, rather than grown by doubling.
+		let (left, right): (Vec<_>, Vec<_>) = range(0u32, 1000).map(|x| (x, x)).collect();
+		assert_eq!((left.capacity(), right.capacity()), (1000, 1000));
+	}
+}
@@ -0,0 +1,37 @@
+use std::{option, result};
+use {IntoTraversal, Internal};
+
+impl<T> IntoTraversal for Option<T> {
+    type IntoTrav = Internal<option::IntoIter<T>>;
+    type Item = T;
+
+    fn into_traversal(self) -> Self::IntoTrav {
+        Internal::new(self)
+    }
+}
+
+impl<T, E> IntoTraversal for Result<T, E> {
+    type IntoTrav = Internal<result::IntoIter<T>>;
+    type Item = T;
+
+    fn into_traversal(self) -> Self::IntoTrav {
+        Internal::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {Traversal, IntoTraversal};
+
+    #[test]
+    fn test_option() {
+        assert_eq!(Some(3).into_traversal().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(None::<u8>.into_traversal().count(), 0);
+    }
+
+    #[test]
+    fn test_result() {
+        assert_eq!(Ok::<_, ()>(3).into_traversal().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(Err::<u8, _>("bad").into_traversal().count(), 0);
+    }
+}
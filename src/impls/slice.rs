@@ -1,26 +1,81 @@
-use {Traversal, Internal};
+use {Traversal, DoubleEndedTraversal, ExactSizeTraversal, IndexedTraversal, Internal, Status, trusted};
 
 impl<'a, T> Traversal for &'a [T] {
     type Item = &'a T;
 
     #[inline]
-    fn foreach<F>(self, f: F) where F: FnMut(&'a T) -> bool {
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(&'a T) -> bool {
         Internal::new(self).foreach(f)
     }
+
+    #[inline]
+    fn foreach_strided<F>(self, start: usize, step: usize, f: F) -> Status
+    where F: FnMut(&'a T) -> bool {
+        Internal::new(self).foreach_strided(start, step, f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = <[T]>::len(self);
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
 }
 
 impl<'a, T> Traversal for &'a mut [T] {
     type Item = &'a mut T;
 
     #[inline]
-    fn foreach<F>(self, f: F) where F: FnMut(&'a mut T) -> bool {
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(&'a mut T) -> bool {
         Internal::new(self).foreach(f)
     }
+
+    #[inline]
+    fn foreach_strided<F>(self, start: usize, step: usize, f: F) -> Status
+    where F: FnMut(&'a mut T) -> bool {
+        Internal::new(self).foreach_strided(start, step, f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = <[T]>::len(self);
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<'a, T> DoubleEndedTraversal for &'a [T] {
+    #[inline]
+    fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(&'a T) -> bool {
+        Internal::new(self).foreach_rev(f)
+    }
+}
+
+impl<'a, T> DoubleEndedTraversal for &'a mut [T] {
+    #[inline]
+    fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(&'a mut T) -> bool {
+        Internal::new(self).foreach_rev(f)
+    }
+}
+
+impl<T> ExactSizeTraversal for &[T] {}
+impl<T> ExactSizeTraversal for &mut [T] {}
+
+impl<'a, T> IndexedTraversal for &'a [T] {
+    #[inline]
+    fn get(&mut self, index: usize) -> &'a T {
+        let slice: &'a [T] = self;
+        &slice[index]
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use Traversal;
+    use {Traversal, ExactSizeTraversal, IndexedTraversal};
 
     #[test]
     fn test_basic() {
@@ -29,6 +84,47 @@ mod test {
         assert_eq!(traversal, data);
     }
 
+    #[test]
+    fn test_len() {
+        let data = [1, 2, 5, 4, 6, 7];
+        assert_eq!(ExactSizeTraversal::len(&&data[..]), 6);
+        assert_eq!(data[1..].map(|&x| x).size_hint(), (5, Some(5)));
+    }
+
+    #[test]
+    fn test_rev() {
+        let data = [1, 2, 5, 4, 6, 7];
+        let traversal: Vec<usize> = data[..].rev().map(|&x| x).collect();
+        assert_eq!(traversal, &[7, 6, 4, 5, 2, 1]);
+
+        let mut data = [1, 2, 3];
+        (&mut data[..]).rev().enumerate().run(|(i, x)| *x *= 10 * i);
+        assert_eq!(data, [20, 20, 0]);
+    }
+
+    #[test]
+    fn test_skip_jumps() {
+        let data = [1, 2, 5, 4, 6, 7];
+        let traversal: Vec<usize> = data[..].skip(2).map(|&x| x).collect();
+        assert_eq!(traversal, &[5, 4, 6, 7]);
+        assert_eq!(data[..].nth(3), Some(&4));
+        assert_eq!(data[..].nth(6), None);
+        let traversal: Vec<usize> = data[..].skip(1).step_by(2).map(|&x| x).collect();
+        assert_eq!(traversal, &[2, 4, 7]);
+    }
+
+    #[test]
+    fn test_zip() {
+        let a = [1, 2, 3, 4];
+        let b = [10, 20, 30];
+        let sums: Vec<usize> = a[..].zip(&b[..]).map(|(x, y)| x + y).collect();
+        assert_eq!(sums, &[11, 22, 33]);
+        assert_eq!(a[..].zip(&b[..]).len(), 3);
+        assert_eq!(a[..].cloned().enumerate().zip(&b[..]).nth(2), Some(((2, 3), &30)));
+        assert_eq!(a[..].skip(1).zip(a[..].rev()).map(|(x, y)| x * y).collect::<Vec<usize>>(),
+                   &[8, 9, 8]);
+    }
+
     #[test]
     fn test_zero_size() {
         let data = [(), (), ()];
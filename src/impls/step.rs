@@ -0,0 +1,107 @@
+use std::char;
+use std::convert::TryFrom;
+use utils::Step;
+
+macro_rules! step_integer {
+    ($($t:ty: $u:ty),*) => {$(
+        impl Step for $t {
+            #[inline]
+            fn steps_between(start: &$t, end: &$t) -> Option<usize> {
+                if start >= end { return Some(0) }
+                // The difference always fits in the unsigned type of the
+                // same width, even when the signed one overflows.
+                usize::try_from((*end as $u).wrapping_sub(*start as $u)).ok()
+            }
+
+            #[inline]
+            fn forward(self, n: usize) -> Option<$t> {
+                let n = <$u>::try_from(n).ok()?;
+                let next = (self as $u).wrapping_add(n) as $t;
+                if next >= self { Some(next) } else { None }
+            }
+
+            #[inline]
+            fn backward(self, n: usize) -> Option<$t> {
+                let n = <$u>::try_from(n).ok()?;
+                let next = (self as $u).wrapping_sub(n) as $t;
+                if next <= self { Some(next) } else { None }
+            }
+        }
+    )*}
+}
+
+step_integer! {
+    u8: u8, u16: u16, u32: u32, u64: u64, u128: u128, usize: usize,
+    i8: u8, i16: u16, i32: u32, i64: u64, i128: u128, isize: usize
+}
+
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATE_LEN: u32 = 0x800;
+
+impl Step for char {
+    #[inline]
+    fn steps_between(start: &char, end: &char) -> Option<usize> {
+        if start >= end { return Some(0) }
+        let (start, end) = (*start as u32, *end as u32);
+        let mut steps = end - start;
+        if start < SURROGATE_START && end > SURROGATE_START {
+            steps -= SURROGATE_LEN;
+        }
+        usize::try_from(steps).ok()
+    }
+
+    #[inline]
+    fn forward(self, n: usize) -> Option<char> {
+        let start = self as u32;
+        let mut next = start.checked_add(u32::try_from(n).ok()?)?;
+        if start < SURROGATE_START && next >= SURROGATE_START {
+            next = next.checked_add(SURROGATE_LEN)?;
+        }
+        char::from_u32(next)
+    }
+
+    #[inline]
+    fn backward(self, n: usize) -> Option<char> {
+        let start = self as u32;
+        let mut next = start.checked_sub(u32::try_from(n).ok()?)?;
+        if start >= SURROGATE_START && next < SURROGATE_START + SURROGATE_LEN {
+            next = next.checked_sub(SURROGATE_LEN)?;
+        }
+        char::from_u32(next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use utils::Step;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(u8::steps_between(&0, &255), Some(255));
+        assert_eq!(u8::steps_between(&5, &1), Some(0));
+        assert_eq!(i8::steps_between(&-128, &127), Some(255));
+        assert_eq!(u128::steps_between(&0, &u128::MAX), None);
+        assert_eq!(255u8.successor(), None);
+        assert_eq!(0u8.predecessor(), None);
+        assert_eq!(127i8.successor(), None);
+        assert_eq!((-128i8).predecessor(), None);
+        assert_eq!((-128i8).forward(255), Some(127));
+        assert_eq!((-128i8).forward(256), None);
+        assert_eq!(127i8.backward(255), Some(-128));
+        assert_eq!(0u8.forward(300), None);
+    }
+
+    #[test]
+    fn test_char() {
+        assert_eq!('a'.successor(), Some('b'));
+        assert_eq!('\u{D7FF}'.successor(), Some('\u{E000}'));
+        assert_eq!('\u{E000}'.predecessor(), Some('\u{D7FF}'));
+        assert_eq!(char::MAX.successor(), None);
+        assert_eq!('\0'.predecessor(), None);
+        assert_eq!(char::steps_between(&'\u{D7FF}', &'\u{E000}'), Some(1));
+        assert_eq!(char::steps_between(&'\0', &char::MAX), Some(0x10FFFF - 0x800));
+        assert_eq!('\0'.forward(0x10FFFF - 0x800), Some(char::MAX));
+        assert_eq!(char::MAX.backward(0x10FFFF - 0x800), Some('\0'));
+        assert_eq!('\u{E000}'.backward(0xE000), None);
+    }
+}
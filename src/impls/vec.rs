@@ -1,5 +1,6 @@
 use std::vec;
-use {Traversal, IntoTraversal, FromTraversal, Internal};
+use {Traversal, DoubleEndedTraversal, ExactSizeTraversal, IndexedTraversal};
+use {IntoTraversal, FromTraversal, Internal, Status, trusted};
 
 impl<T> IntoTraversal for Vec<T> {
     type IntoTrav = Internal<vec::IntoIter<T>>;
@@ -10,6 +11,47 @@ impl<T> IntoTraversal for Vec<T> {
     }
 }
 
+impl<'a, T> Traversal for &'a Vec<T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn foreach<F>(self, f: F) -> Status where F: FnMut(&'a T) -> bool {
+        self[..].foreach(f)
+    }
+
+    #[inline]
+    fn foreach_strided<F>(self, start: usize, step: usize, f: F) -> Status
+    where F: FnMut(&'a T) -> bool {
+        self[..].foreach_strided(start, step, f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = Vec::len(self);
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<'a, T> DoubleEndedTraversal for &'a Vec<T> {
+    #[inline]
+    fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(&'a T) -> bool {
+        self[..].foreach_rev(f)
+    }
+}
+
+impl<T> ExactSizeTraversal for &Vec<T> {}
+
+impl<'a, T> IndexedTraversal for &'a Vec<T> {
+    #[inline]
+    fn get(&mut self, index: usize) -> &'a T {
+        let vec: &'a Vec<T> = self;
+        &vec[index]
+    }
+}
+
 impl<T> FromTraversal<T> for Vec<T> {
 	fn from_traversal<I: IntoTraversal<Item=T>>(traversable: I) -> Self {
 		let trav = traversable.into_traversal();
@@ -23,7 +65,8 @@ impl<T> FromTraversal<T> for Vec<T> {
 
 #[cfg(test)]
 mod test {
-    use {Traversal, IntoTraversal};
+    use {Traversal, IntoTraversal, IndexedTraversal};
+    use utils::range;
 
     #[test]
     fn test_basic() {
@@ -32,6 +75,28 @@ mod test {
         assert_eq!(traversal, data);
     }
 
+    #[test]
+    fn test_preallocates() {
+        let vec: Vec<u32> = range(0, 1000000).collect();
+        assert_eq!(vec.capacity(), 1000000);
+    }
+
+    #[test]
+    fn test_rev() {
+        let data = vec![1, 2, 5, 4, 6, 7];
+        let traversal: Vec<usize> = data.into_traversal().rev().collect();
+        assert_eq!(traversal, &[7, 6, 4, 5, 2, 1]);
+    }
+
+    #[test]
+    fn test_borrowed() {
+        let data = vec![1, 2, 5, 4, 6, 7];
+        let traversal: Vec<usize> = (&data).skip(3).cloned().collect();
+        assert_eq!(traversal, &[4, 6, 7]);
+        assert_eq!((&data).zip(&data).nth(2), Some((&5, &5)));
+        assert_eq!((&data).rev().nth(0), Some(&7));
+    }
+
     #[test]
     fn test_zero_size() {
         let data = vec![(), (), ()];
@@ -6,31 +6,71 @@
 #[cfg(all(test, feature = "nightly"))] extern crate test;
 #[cfg(all(test, feature = "nightly"))] extern crate rand;
 
-// For CheckedAdd
+// For the numeric traits behind the ranges and the arithmetic consumers
 extern crate num;
 
+use std::cmp::{self, Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::convert::Infallible;
+use num::traits::{Zero, One, CheckedAdd, CheckedMul, Saturating, Float};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::mpsc;
+use std::thread;
+use std::vec;
+
 mod ext;
 pub mod utils;
+pub mod stats;
+pub mod collector;
 mod impls;
 
+pub use collector::Collector;
+
 /// An iterator that runs all at once
 pub trait Traversal: Sized {
     type Item;
 
     /// Run this Iterator using the provided closure.
     ///
-    /// Return true from the closure to end the iteration.
-    fn foreach<F>(self, F) where F: FnMut(Self::Item) -> bool;
+    /// Return true from the closure to end the iteration. The returned
+    /// `Status` says whether the iteration ran to completion or was ended
+    /// by the closure.
+    fn foreach<F>(self, F) -> Status where F: FnMut(Self::Item) -> bool;
 
     /// Run this Iterator using the provided closure.
     ///
     /// This is a utility method for non-cancelling iterations.
     fn run<F>(self, mut f: F) where F: FnMut(Self::Item) {
-        self.foreach(|t| { f(t); false })
+        self.foreach(|t| { f(t); false });
+    }
+
+    /// Run this Iterator over only the elements at `start`, `start + step`,
+    /// `start + 2 * step` and so on, counting from zero.
+    ///
+    /// This is what `skip`, `step_by` and `nth` are built on. By default the
+    /// elements in between are produced and thrown away; sources that can
+    /// jump ahead cheaply, like slices and ranges, override it. Adapters
+    /// without closures, like `enumerate` and `skip`, pass it through;
+    /// adapters with closures, like `map` and `inspect`, still run them on
+    /// every element jumped over.
+    fn foreach_strided<F>(self, start: usize, step: usize, f: F) -> Status
+    where F: FnMut(Self::Item) -> bool {
+        walk_strided(self, start, step, f)
     }
 
+    /// Return bounds on the number of elements this will produce.
+    ///
+    /// The lower bound is used to pre-allocate when collecting.
     fn size_hint(&self) -> (usize, Option<usize>) { (0, None) }
 
+    /// Return the exact number of elements, if it is known for certain and
+    /// the whole traversal can be jumped over without side effects.
+    ///
+    /// `chain` uses this to decide whether it may skip its first half
+    /// without running it. The default is `None`.
+    fn trusted_len(&self) -> Option<usize> { None }
+
     fn map<F, O>(self, f: F) -> Map<Self, F>
     where F: FnMut(Self::Item) -> O {
         Map { iter: self, closure: f }
@@ -58,6 +98,18 @@ pub trait Traversal: Sized {
         Take { iter: self, n: n }
     }
 
+    /// Yield every `step`th element, starting with the first.
+    ///
+    /// This goes through `foreach_strided`, so the range sources, slices
+    /// and other indexed traversals jump straight from one element to the
+    /// next instead of producing the ones in between.
+    ///
+    /// Panics if `step` is zero.
+    fn step_by(self, step: usize) -> StepBy<Self> {
+        assert!(step != 0, "step_by: step must not be zero");
+        StepBy { iter: self, step: step }
+    }
+
     fn skip_while<F>(self, pred: F) -> SkipWhile<Self, F>
     where F: FnMut(&Self::Item) -> bool {
         SkipWhile { iter: self, predicate: pred }
@@ -68,36 +120,792 @@ pub trait Traversal: Sized {
         TakeWhile { iter: self, predicate: pred }
     }
 
+    /// Yield `f(t)` while it returns `Some`, stopping at the first `None`.
+    fn map_while<F, O>(self, f: F) -> MapWhile<Self, F>
+    where F: FnMut(Self::Item) -> Option<O> {
+        MapWhile { iter: self, closure: f }
+    }
+
+    /// Thread a piece of mutable state through the traversal, yielding
+    /// whatever `f` returns until it returns `None`.
+    ///
+    /// Running totals are the typical use:
+    ///
+    /// ```
+    /// use traverse::Traversal;
+    /// use traverse::utils::range;
+    ///
+    /// let sums: Vec<i32> = range(1, 5).scan(0, |total, x| {
+    ///     *total += x;
+    ///     Some(*total)
+    /// }).collect();
+    /// assert_eq!(sums, vec![1, 3, 6, 10]);
+    /// ```
+    fn scan<St, F, O>(self, initial_state: St, f: F) -> Scan<Self, St, F>
+    where F: FnMut(&mut St, Self::Item) -> Option<O> {
+        Scan { iter: self, state: initial_state, closure: f }
+    }
+
+    /// Merge runs of adjacent elements.
+    ///
+    /// `f` is given the pending element and the next one, and returns
+    /// `Ok(merged)` to carry on with `merged` as the pending element, or
+    /// `Err((pending, next))` to yield `pending` and carry on with `next`.
+    /// The last pending element is yielded when the traversal finishes.
+    fn coalesce<F>(self, f: F) -> Coalesce<Self, F>
+    where F: FnMut(Self::Item, Self::Item)
+                 -> Result<Self::Item, (Self::Item, Self::Item)> {
+        Coalesce { iter: self, f: f }
+    }
+
+    /// Collapse runs of equal elements into their first element.
+    fn dedup(self) -> Dedup<Self> where Self::Item: PartialEq {
+        Coalesce { iter: self, f: DedupEq }
+    }
+
+    /// Collapse runs of elements into their first element, where a run
+    /// goes on for as long as `same(first, next)` holds.
+    fn dedup_by<F>(self, same: F) -> DedupBy<Self, F>
+    where F: FnMut(&Self::Item, &Self::Item) -> bool {
+        Coalesce { iter: self, f: DedupPred(same) }
+    }
+
+    /// Collapse runs of elements with equal keys into their first element.
+    fn dedup_by_key<F, K>(self, key: F) -> DedupByKey<Self, F>
+    where F: FnMut(&Self::Item) -> K, K: PartialEq {
+        Coalesce { iter: self, f: DedupKey(key) }
+    }
+
+    /// Collapse runs of equal elements into their first element, paired
+    /// with the length of the run.
+    fn dedup_with_count(self) -> DedupWithCount<Self> where Self::Item: PartialEq {
+        let first: fn(Self::Item) -> (usize, Self::Item) = run_of_one;
+        Coalesce { iter: self.map(first), f: DedupCount }
+    }
+
+    /// Group runs of adjacent elements with equal keys, yielding each key
+    /// along with its run.
+    ///
+    /// Use `GroupBy::foreach_events` to see the groups without collecting
+    /// them.
+    fn group_by<F, K>(self, key: F) -> GroupBy<Self, F>
+    where F: FnMut(&Self::Item) -> K, K: PartialEq {
+        GroupBy { iter: self, key: key }
+    }
+
+    /// Split the elements into the segments between delimiters, dropping
+    /// the delimiters themselves.
+    ///
+    /// Like `str::split`, `n` delimiters always make `n + 1` segments, some
+    /// of which may be empty. Use `Split::foreach_events` to see the
+    /// segments without collecting them.
+    fn split<P>(self, is_delimiter: P) -> Split<Self, P>
+    where P: FnMut(&Self::Item) -> bool {
+        Split { iter: self, predicate: is_delimiter }
+    }
+
+    /// Yield a clone of `separator` between each pair of elements.
+    fn intersperse(self, separator: Self::Item) -> Intersperse<Self, Self::Item>
+    where Self::Item: Clone {
+        Intersperse { iter: self, separator: separator }
+    }
+
+    /// Yield the result of `separator()` between each pair of elements.
+    fn intersperse_with<F>(self, separator: F) -> IntersperseWith<Self, F>
+    where F: FnMut() -> Self::Item {
+        IntersperseWith { iter: self, separator: separator }
+    }
+
+    /// Pair each element with where it falls in the traversal.
+    ///
+    /// Each element is held back until the next one arrives, to tell
+    /// whether it is the last.
+    fn with_position(self) -> WithPosition<Self> {
+        WithPosition { iter: self }
+    }
+
     fn inspect<F>(self, f: F) -> Inspect<Self, F>
     where F: FnMut(&Self::Item) {
         Inspect { iter: self, closure: f }
     }
 
+    /// Yield every pair of neighbouring elements, `(previous, current)`.
+    ///
+    /// Nothing is yielded if there are fewer than two elements.
+    fn pairwise(self) -> TupleWindows<Self, (Self::Item, Self::Item)>
+    where Self::Item: Clone {
+        self.tuple_windows()
+    }
+
+    /// Yield every run of neighbouring elements as a tuple, such as
+    /// `(a, b)` or `(a, b, c)`.
+    ///
+    /// Nothing is yielded if there are fewer elements than the tuple holds.
+    fn tuple_windows<W>(self) -> TupleWindows<Self, W>
+    where W: TupleWindow<Self::Item> {
+        TupleWindows { iter: self, marker: PhantomData }
+    }
+
+    /// Yield every run of `size` neighbouring elements.
+    ///
+    /// As a traversal each window is copied into a `Vec`; use
+    /// `Windows::foreach_view` to borrow it in place instead. Nothing is
+    /// yielded if there are fewer than `size` elements.
+    ///
+    /// Panics if `size` is zero.
+    fn windows(self, size: usize) -> Windows<Self> {
+        assert!(size != 0, "windows: size must not be zero");
+        Windows { iter: self, size: size }
+    }
+
+    /// Yield the elements in batches of `size`, the last of which may be
+    /// shorter.
+    ///
+    /// Panics if `size` is zero.
+    fn chunks(self, size: usize) -> Chunks<Self> {
+        assert!(size != 0, "chunks: size must not be zero");
+        Chunks { iter: self, size: size }
+    }
+
+    /// Yield the elements in batches of exactly `size`.
+    ///
+    /// Leftover elements are dropped; use
+    /// `ChunksExact::foreach_with_remainder` to get them back.
+    ///
+    /// Panics if `size` is zero.
+    fn chunks_exact(self, size: usize) -> ChunksExact<Self> {
+        assert!(size != 0, "chunks_exact: size must not be zero");
+        ChunksExact { iter: self, size: size }
+    }
+
+    /// Yield the elements in batches whose summed `weight` is at most
+    /// `max`.
+    ///
+    /// A batch is cut whenever the next element would take it over the
+    /// budget, and an element heavier than `max` on its own gets a batch to
+    /// itself.
+    fn batch_by_weight<F>(self, max: usize, weight: F) -> BatchByWeight<Self, F>
+    where F: FnMut(&Self::Item) -> usize {
+        BatchByWeight { iter: self, max: max, weight: weight }
+    }
+
+    /// Run the traversal produced from each element in turn.
+    ///
+    /// `f` may return anything that turns into a traversal, such as a
+    /// `Vec` or an `Option`.
     fn flat_map<A, U, F>(self, f: F) -> FlatMap<Self, F>
-    where U: Traversal<Item=A>,
+    where U: IntoTraversal<Item=A>,
           F: FnMut(Self::Item) -> U {
         FlatMap { iter: self, producer: f }
     }
 
+    /// Run the iterator produced from each element in turn.
+    fn flat_map_iter<U, F>(self, f: F) -> FlatMapIter<Self, F>
+    where U: IntoIterator,
+          F: FnMut(Self::Item) -> U {
+        FlatMapIter { iter: self, producer: f }
+    }
+
+    /// Run each element as a traversal in turn.
+    ///
+    /// An `Option` or `Result` element counts as a traversal of zero or one
+    /// elements, so this also drops the `None`s or `Err`s.
+    fn flatten(self) -> Flatten<Self> where Self::Item: IntoTraversal {
+        Flatten { iter: self }
+    }
+
     fn chain<O>(self, other: O) -> Chain<Self, O>
     where O: Traversal<Item=Self::Item> {
         Chain { one: self, two: other }
     }
 
+    /// Pair each element with the next one from `other`, stopping as soon
+    /// as either side runs out.
+    ///
+    /// This traversal drives and `other` is advanced from inside it, so it
+    /// works for any traversal, not only indexed ones.
+    fn zip_iter<J>(self, other: J) -> ZipIter<Self, J::IntoIter>
+    where J: IntoIterator {
+        ZipIter { iter: self, other: other.into_iter() }
+    }
+
+    /// Pair each element with the next one from `other`, carrying on with
+    /// whichever side is longer once the other runs out.
+    fn zip_longest_iter<J>(self, other: J) -> ZipLongestIter<Self, J::IntoIter>
+    where J: IntoIterator {
+        ZipLongestIter { iter: self, other: other.into_iter() }
+    }
+
+    /// Run this traversal over and over again, forever.
+    ///
+    /// Each pass runs a fresh clone. If a pass yields nothing the cycle
+    /// finishes rather than spinning.
+    fn cycle(self) -> Cycle<Self> where Self: Clone {
+        Cycle { iter: self }
+    }
+
+    /// Run this traversal `n` times over, one after the other.
+    ///
+    /// Its `len` panics if the total length does not fit in a usize.
+    fn cycle_n(self, n: usize) -> CycleN<Self> where Self: Clone {
+        CycleN { iter: self, n: n }
+    }
+
     fn count(self) -> usize {
         let mut count = 0;
         self.run(|_| { count += 1; });
         count
     }
 
+    /// Return the first element satisfying `pred`, stopping the traversal
+    /// as soon as it is found.
+    fn find<P>(self, mut pred: P) -> Option<Self::Item>
+    where P: FnMut(&Self::Item) -> bool {
+        let mut found = None;
+        self.foreach(|t| {
+            if pred(&t) { found = Some(t); true } else { false }
+        });
+        found
+    }
+
+    /// Return the first `Some` produced by `f`, stopping the traversal
+    /// as soon as it is found.
+    fn find_map<F, O>(self, mut f: F) -> Option<O>
+    where F: FnMut(Self::Item) -> Option<O> {
+        let mut found = None;
+        self.foreach(|t| {
+            found = f(t);
+            found.is_some()
+        });
+        found
+    }
+
+    /// Return the index of the first element satisfying `pred`.
+    fn position<P>(self, mut pred: P) -> Option<usize>
+    where P: FnMut(Self::Item) -> bool {
+        let mut index = 0;
+        let mut found = None;
+        self.foreach(|t| {
+            if pred(t) { found = Some(index); true } else { index += 1; false }
+        });
+        found
+    }
+
+    /// Return the index of the last element satisfying `pred`.
+    ///
+    /// A traversal can only be run front to back, so this always runs
+    /// to completion.
+    fn rposition<P>(self, mut pred: P) -> Option<usize>
+    where P: FnMut(Self::Item) -> bool {
+        let mut index = 0;
+        let mut found = None;
+        self.run(|t| {
+            if pred(t) { found = Some(index) }
+            index += 1;
+        });
+        found
+    }
+
+    /// Return true if any element satisfies `pred`, stopping at the first
+    /// one that does.
+    fn any<P>(self, pred: P) -> bool
+    where P: FnMut(Self::Item) -> bool {
+        self.foreach(pred).is_stopped()
+    }
+
+    /// Return true if every element satisfies `pred`, stopping at the
+    /// first one that does not.
+    fn all<P>(self, mut pred: P) -> bool
+    where P: FnMut(Self::Item) -> bool {
+        self.foreach(|t| !pred(t)).is_done()
+    }
+
+    /// Return the `n`th element (counting from zero), stopping the
+    /// traversal there.
+    fn nth(self, n: usize) -> Option<Self::Item> {
+        let mut found = None;
+        self.foreach_strided(n, 1, |t| { found = Some(t); true });
+        found
+    }
+
+    /// Return the last element, running the traversal to completion.
+    fn last(self) -> Option<Self::Item> {
+        let mut last = None;
+        self.run(|t| { last = Some(t); });
+        last
+    }
+
+    /// Fold every element into an accumulator, stopping early if `f`
+    /// returns a break value (an `Err`, a `None`, a `ControlFlow::Break`).
+    ///
+    /// The break value is handed back to the caller, so errors from deep
+    /// inside a pipeline propagate out with `?`.
+    fn try_fold<B, F, R>(self, init: B, mut f: F) -> R
+    where F: FnMut(B, Self::Item) -> R,
+          R: Try<Output=B> {
+        let mut acc = Some(init);
+        let mut residual = None;
+        self.foreach(|t| {
+            // acc is only None once we have broken out.
+            match acc.take() {
+                Some(a) => match f(a, t).branch() {
+                    Ok(a) => { acc = Some(a); false },
+                    Err(r) => { residual = Some(r); true }
+                },
+                None => true
+            }
+        });
+
+        match residual {
+            Some(r) => R::from_residual(r),
+            None => R::from_output(acc.unwrap())
+        }
+    }
+
+    /// Run `f` on every element, stopping at and returning the first
+    /// break value it produces.
+    fn try_for_each<F, R>(self, mut f: F) -> R
+    where F: FnMut(Self::Item) -> R,
+          R: Try<Output=()> {
+        self.try_fold((), move |(), t| f(t))
+    }
+
+    /// Fold every element into an accumulator.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where F: FnMut(B, Self::Item) -> B {
+        match self.try_fold(init, move |acc, t| Ok::<B, Infallible>(f(acc, t))) {
+            Ok(acc) => acc,
+            Err(never) => match never {}
+        }
+    }
+
+    /// Fold the elements together using the first one as the initial
+    /// accumulator, returning `None` for an empty traversal.
+    fn reduce<F>(self, mut f: F) -> Option<Self::Item>
+    where F: FnMut(Self::Item, Self::Item) -> Self::Item {
+        self.fold(None, move |acc, t| Some(match acc {
+            Some(acc) => f(acc, t),
+            None => t
+        }))
+    }
+
+    /// Format every element into a `String`, with `separator` between
+    /// them.
+    fn join(self, separator: &str) -> String where Self::Item: fmt::Display {
+        let mut out = String::new();
+        self.join_to(&mut out, separator)
+            .expect("a Display implementation returned an error unexpectedly");
+        out
+    }
+
+    /// Format every element into `out`, with `separator` between them,
+    /// stopping at the first error.
+    fn join_to<W>(self, out: &mut W, separator: &str) -> fmt::Result
+    where W: fmt::Write, Self::Item: fmt::Display {
+        let mut first = true;
+        self.try_for_each(|t| {
+            if !first { out.write_str(separator)?; }
+            first = false;
+            write!(out, "{}", t)
+        })
+    }
+
+    /// Wrap this in something that formats as its elements joined by
+    /// `separator`.
+    ///
+    /// The traversal is cloned each time it is formatted.
+    fn display_with<'a>(self, separator: &'a str) -> DisplayWith<'a, Self>
+    where Self: Clone, Self::Item: fmt::Display {
+        DisplayWith { iter: self, separator: separator }
+    }
+
+    /// Add up the elements, starting from zero.
+    ///
+    /// Overflow behaves as it does for `+`: a panic in debug builds and
+    /// wrapping in release builds. Use `checked_sum` or `saturating_sum`
+    /// where that matters.
+    fn sum(self) -> Self::Item where Self::Item: Zero {
+        self.fold(Zero::zero(), |acc, t| acc + t)
+    }
+
+    /// Multiply the elements together, starting from one.
+    ///
+    /// Overflow behaves as it does for `*`; use `checked_product` where
+    /// that matters.
+    fn product(self) -> Self::Item where Self::Item: One {
+        self.fold(One::one(), |acc, t| acc * t)
+    }
+
+    /// Add up the elements, returning `None` as soon as the total
+    /// overflows.
+    fn checked_sum(self) -> Option<Self::Item> where Self::Item: Zero + CheckedAdd {
+        self.try_fold(Zero::zero(), |acc: Self::Item, t| acc.checked_add(&t))
+    }
+
+    /// Multiply the elements together, returning `None` as soon as the
+    /// product overflows.
+    fn checked_product(self) -> Option<Self::Item> where Self::Item: One + CheckedMul {
+        self.try_fold(One::one(), |acc: Self::Item, t| acc.checked_mul(&t))
+    }
+
+    /// Add up the elements, clamping the total at the numeric bounds.
+    fn saturating_sum(self) -> Self::Item where Self::Item: Zero + Saturating {
+        self.fold(Zero::zero(), |acc: Self::Item, t| acc.saturating_add(t))
+    }
+
+    /// Add up floating point elements, keeping track of the rounding error
+    /// along the way (Neumaier's variant of Kahan summation).
+    ///
+    /// This is much more accurate than `sum` when the elements differ
+    /// greatly in magnitude, at the cost of a few more operations each.
+    fn compensated_sum(self) -> Self::Item where Self::Item: Float {
+        let zero: Self::Item = Zero::zero();
+        let (sum, compensation) = self.fold((zero, zero), |(sum, compensation), t| {
+            let next = sum + t;
+            let lost = if sum.abs() >= t.abs() { (sum - next) + t } else { (t - next) + sum };
+            (next, compensation + lost)
+        });
+        sum + compensation
+    }
+
+    /// Return the greatest element, or the last of them if several are
+    /// equally great.
+    fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+        self.max_by(Ord::cmp)
+    }
+
+    /// Return the least element, or the first of them if several are
+    /// equally small.
+    fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+        self.min_by(Ord::cmp)
+    }
+
+    /// Return the greatest element according to `compare`, or the last of
+    /// them if several are equally great.
+    ///
+    /// `compare` must be a total order; for floats, `f64::total_cmp` is one.
+    fn max_by<F>(self, mut compare: F) -> Option<Self::Item>
+    where F: FnMut(&Self::Item, &Self::Item) -> Ordering {
+        self.reduce(|max, t| match compare(&max, &t) {
+            Ordering::Greater => max,
+            _ => t
+        })
+    }
+
+    /// Return the least element according to `compare`, or the first of
+    /// them if several are equally small.
+    ///
+    /// `compare` must be a total order; for floats, `f64::total_cmp` is one.
+    fn min_by<F>(self, mut compare: F) -> Option<Self::Item>
+    where F: FnMut(&Self::Item, &Self::Item) -> Ordering {
+        self.reduce(|min, t| match compare(&min, &t) {
+            Ordering::Greater => t,
+            _ => min
+        })
+    }
+
+    /// Return the element with the greatest key, or the last of them if
+    /// several keys are equally great.
+    ///
+    /// `key` is called once per element.
+    fn max_by_key<B, F>(self, mut key: F) -> Option<Self::Item>
+    where B: Ord, F: FnMut(&Self::Item) -> B {
+        self.map(|t| (key(&t), t)).max_by(|a, b| a.0.cmp(&b.0)).map(|(_, t)| t)
+    }
+
+    /// Return the element with the least key, or the first of them if
+    /// several keys are equally small.
+    ///
+    /// `key` is called once per element.
+    fn min_by_key<B, F>(self, mut key: F) -> Option<Self::Item>
+    where B: Ord, F: FnMut(&Self::Item) -> B {
+        self.map(|t| (key(&t), t)).min_by(|a, b| a.0.cmp(&b.0)).map(|(_, t)| t)
+    }
+
+    /// Return both the least and the greatest element in a single pass,
+    /// with the same tie-breaking as `min` and `max`.
+    fn minmax(self) -> MinMaxResult<Self::Item> where Self::Item: Ord {
+        self.minmax_by(Ord::cmp)
+    }
+
+    /// Return both the least and the greatest element according to
+    /// `compare` in a single pass.
+    ///
+    /// `compare` must be a total order; for floats, `f64::total_cmp` is one.
+    fn minmax_by<F>(self, mut compare: F) -> MinMaxResult<Self::Item>
+    where F: FnMut(&Self::Item, &Self::Item) -> Ordering {
+        use MinMaxResult::*;
+        self.fold(NoElements, |acc, t| match acc {
+            NoElements => OneElement(t),
+            OneElement(first) => match compare(&first, &t) {
+                Ordering::Greater => MinMax(t, first),
+                _ => MinMax(first, t)
+            },
+            MinMax(min, max) => {
+                if compare(&min, &t) == Ordering::Greater {
+                    MinMax(t, max)
+                } else if compare(&max, &t) != Ordering::Greater {
+                    MinMax(min, t)
+                } else {
+                    MinMax(min, max)
+                }
+            }
+        })
+    }
+
+    /// Return the `k` least elements in ascending order.
+    ///
+    /// Only `k` elements are held at a time.
+    fn k_smallest(self, k: usize) -> Internal<vec::IntoIter<Self::Item>>
+    where Self::Item: Ord {
+        if k == 0 { return Internal::new(Vec::new()) }
+
+        // A max-heap of the k least so far, so the one to evict is on top.
+        // Reserve for no more than the input, since k may mean "all".
+        let mut heap = BinaryHeap::with_capacity(cmp::min(k, self.size_hint().0));
+        self.run(|t| {
+            if heap.len() < k {
+                heap.push(t);
+            } else if let Some(mut top) = heap.peek_mut() {
+                if t < *top { *top = t; }
+            }
+        });
+        Internal::new(heap.into_sorted_vec())
+    }
+
+    /// Return the `k` greatest elements in descending order.
+    ///
+    /// Only `k` elements are held at a time.
+    fn k_largest(self, k: usize) -> Internal<vec::IntoIter<Self::Item>>
+    where Self::Item: Ord {
+        if k == 0 { return Internal::new(Vec::new()) }
+
+        let mut heap = BinaryHeap::with_capacity(cmp::min(k, self.size_hint().0));
+        self.run(|t| {
+            if heap.len() < k {
+                heap.push(Reverse(t));
+            } else if let Some(mut top) = heap.peek_mut() {
+                if t > top.0 { *top = Reverse(t); }
+            }
+        });
+        let largest: Vec<_> = heap.into_sorted_vec().into_iter().map(|Reverse(t)| t).collect();
+        Internal::new(largest)
+    }
+
+    /// Collect the elements and traverse them in sorted order.
+    ///
+    /// The sort is stable.
+    fn sorted(self) -> Internal<vec::IntoIter<Self::Item>> where Self::Item: Ord {
+        let mut all: Vec<_> = self.collect();
+        all.sort();
+        Internal::new(all)
+    }
+
+    /// Collect the elements and traverse them in order of `compare`.
+    ///
+    /// The sort is stable.
+    fn sorted_by<F>(self, compare: F) -> Internal<vec::IntoIter<Self::Item>>
+    where F: FnMut(&Self::Item, &Self::Item) -> Ordering {
+        let mut all: Vec<_> = self.collect();
+        all.sort_by(compare);
+        Internal::new(all)
+    }
+
+    /// Collect the elements and traverse them in order of their keys.
+    ///
+    /// The sort is stable.
+    fn sorted_by_key<B, F>(self, key: F) -> Internal<vec::IntoIter<Self::Item>>
+    where B: Ord, F: FnMut(&Self::Item) -> B {
+        let mut all: Vec<_> = self.collect();
+        all.sort_by_key(key);
+        Internal::new(all)
+    }
+
     fn cloned(self) -> Cloned<Self> {
         Cloned { iter: self }
     }
 
+    /// Run this traversal back to front.
+    fn rev(self) -> Rev<Self> where Self: DoubleEndedTraversal {
+        Rev { iter: self }
+    }
+
     fn collect<D>(self) -> D
     where D: FromTraversal<Self::Item> {
         FromTraversal::from_traversal(self)
     }
+
+    /// Reduce the elements with `collector`, stopping early once it has
+    /// seen all it needs.
+    fn collect_with<C>(self, mut collector: C) -> C::Output
+    where C: Collector<Self::Item> {
+        let mut acc = collector.init();
+        self.foreach(|t| collector.accumulate(&mut acc, t));
+        collector.finish(acc)
+    }
+
+    /// Split the elements into those for which `pred` holds and those for
+    /// which it does not, in a single pass.
+    fn partition<B, P>(self, mut pred: P) -> (B, B)
+    where B: Default + Extend<Self::Item>,
+          P: FnMut(&Self::Item) -> bool {
+        let (mut left, mut right) = (B::default(), B::default());
+        self.run(|t| {
+            if pred(&t) { left.extend(Some(t)) } else { right.extend(Some(t)) }
+        });
+        (left, right)
+    }
+
+    /// Split a traversal of pairs into a collection of the first halves
+    /// and one of the second halves, in a single pass.
+    fn unzip<A, B, FromA, FromB>(self) -> (FromA, FromB)
+    where Self: Traversal<Item=(A, B)>,
+          FromA: Default + Extend<A>,
+          FromB: Default + Extend<B> {
+        self.collect()
+    }
+
+    /// Turn this into a pull-based `Iterator` by running it on a helper
+    /// thread.
+    ///
+    /// Shorthand for `spawn_iter_bounded(SPAWN_ITER_BOUND)`. As described
+    /// there, dropping the iterator waits for the traversal's next element,
+    /// which may never come.
+    fn spawn_iter(self) -> SpawnIter<Self::Item>
+    where Self: Send + 'static, Self::Item: Send + 'static {
+        self.spawn_iter_bounded(SPAWN_ITER_BOUND)
+    }
+
+    /// Turn this into a pull-based `Iterator` by running it on a helper
+    /// thread, which may get at most `bound` elements ahead of the consumer.
+    ///
+    /// Dropping the iterator tells the traversal to stop the next time it
+    /// hands over an element, and then waits for the thread to finish. A
+    /// panic in the traversal is resumed in the consumer when it reaches
+    /// that point, or when the iterator is dropped.
+    ///
+    /// **Dropping blocks until the traversal next hands over an element or
+    /// ends.** A traversal that never does either, like
+    /// `count(0, 1).filter(|x| *x < 5)` after its fifth element, makes the
+    /// drop hang forever. Only drop such an iterator early if the traversal
+    /// is sure to produce or finish.
+    fn spawn_iter_bounded(self, bound: usize) -> SpawnIter<Self::Item>
+    where Self: Send + 'static, Self::Item: Send + 'static {
+        let hint = self.size_hint();
+        let (tx, rx) = mpsc::sync_channel(bound);
+        let producer = thread::spawn(move || {
+            self.foreach(|t| tx.send(t).is_err());
+        });
+        SpawnIter { rx: rx, producer: Some(producer), hint: hint }
+    }
+}
+
+/// How far ahead of its consumer `spawn_iter` lets a traversal run.
+pub const SPAWN_ITER_BOUND: usize = 64;
+
+/// A traversal that knows exactly how many elements it will produce.
+pub trait ExactSizeTraversal: Traversal {
+    /// Return the exact number of elements this will produce.
+    #[inline]
+    fn len(&self) -> usize {
+        let (lower, upper) = self.size_hint();
+        debug_assert_eq!(upper, Some(lower));
+        lower
+    }
+
+    /// Return true if this will produce no elements.
+    #[inline]
+    fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+/// A traversal that can also be run from back to front.
+pub trait DoubleEndedTraversal: Traversal {
+    /// Run this Iterator backwards using the provided closure.
+    ///
+    /// Return true from the closure to end the iteration.
+    fn foreach_rev<F>(self, F) -> Status where F: FnMut(Self::Item) -> bool;
+}
+
+/// A traversal whose elements can be produced one at a time by index.
+///
+/// Two indexed traversals can be zipped together, and jumping to any
+/// position in one is cheap.
+pub trait IndexedTraversal: ExactSizeTraversal {
+    /// Produce the element at `index`.
+    ///
+    /// `index` must be less than `len()`. Elements may be asked for in any
+    /// order, and each at most once.
+    fn get(&mut self, index: usize) -> Self::Item;
+
+    /// Traverse this and `other` in lockstep, yielding pairs of elements
+    /// until the shorter of the two runs out.
+    fn zip<O>(self, other: O) -> Zip<Self, O> where O: IndexedTraversal {
+        Zip { one: self, two: other }
+    }
+}
+
+/// The default `foreach_strided`, which produces every element and drops
+/// the ones not asked for.
+fn walk_strided<T, F>(trav: T, start: usize, step: usize, mut f: F) -> Status
+where T: Traversal, F: FnMut(T::Item) -> bool {
+    let mut skip = start;
+    trav.foreach(|t| {
+        if skip == 0 {
+            skip = step - 1;
+            f(t)
+        } else {
+            skip -= 1;
+            false
+        }
+    })
+}
+
+/// The `trusted_len` of a source whose `size_hint` is exact.
+fn trusted(hint: (usize, Option<usize>)) -> Option<usize> {
+    match hint {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None
+    }
+}
+
+/// Whether a traversal ran to completion or was stopped early by the
+/// closure driving it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Every element was produced.
+    Done,
+    /// The closure returned true and the traversal ended early.
+    Stopped
+}
+
+impl Status {
+    /// Did the traversal run to completion?
+    #[inline]
+    pub fn is_done(self) -> bool { self == Status::Done }
+
+    /// Was the traversal ended early?
+    #[inline]
+    pub fn is_stopped(self) -> bool { self == Status::Stopped }
+}
+
+/// A value that either continues a fallible traversal with an `Output` or
+/// stops it with a `Residual`.
+///
+/// Implemented for `Result`, `Option` and `std::ops::ControlFlow`.
+pub trait Try {
+    /// The value carried when continuing.
+    type Output;
+    /// The value carried when stopping.
+    type Residual;
+
+    /// Wrap a value that continues the traversal.
+    fn from_output(output: Self::Output) -> Self;
+
+    /// Wrap a value that stops the traversal.
+    fn from_residual(residual: Self::Residual) -> Self;
+
+    /// Split into `Ok` to continue or `Err` to stop.
+    fn branch(self) -> Result<Self::Output, Self::Residual>;
 }
 
 pub trait FromTraversal<T> {
@@ -132,13 +940,39 @@ impl<I: Iterator> Internal<I> {
 impl<I: Iterator> Traversal for Internal<I> {
     type Item = I::Item;
 
-    fn foreach<F>(self, mut f: F) where F: FnMut(I::Item) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(I::Item) -> bool {
         for elem in self.iter {
-            if f(elem) { break }
+            if f(elem) { return Status::Stopped }
+        }
+        Status::Done
+    }
+
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(I::Item) -> bool {
+        let mut iter = self.iter;
+        let mut next = iter.nth(start);
+        while let Some(elem) = next {
+            if f(elem) { return Status::Stopped }
+            next = iter.nth(step - 1);
         }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedTraversal for Internal<I> {
+    fn foreach_rev<F>(self, mut f: F) -> Status where F: FnMut(I::Item) -> bool {
+        for elem in self.iter.rev() {
+            if f(elem) { return Status::Stopped }
+        }
+        Status::Done
     }
 }
 
+impl<I: ExactSizeIterator> ExactSizeTraversal for Internal<I> {}
+
 /// An Traversal that maps over the contents of
 /// another Traversal.
 #[derive(Copy, Clone)]
@@ -186,6 +1020,125 @@ pub struct TakeWhile<I, F> {
     predicate: F
 }
 
+#[derive(Copy, Clone)]
+pub struct MapWhile<I, F> {
+    iter: I,
+    closure: F
+}
+
+#[derive(Copy, Clone)]
+pub struct Scan<I, St, F> {
+    iter: I,
+    state: St,
+    closure: F
+}
+
+#[derive(Copy, Clone)]
+pub struct Coalesce<I, F> {
+    iter: I,
+    f: F
+}
+
+/// A rule for merging two adjacent elements, as used by `Coalesce`.
+///
+/// Implemented for closures passed to `coalesce` and for the rules behind
+/// the `dedup` family.
+pub trait CoalescePredicate<T> {
+    /// Return `Ok(merged)` to merge `pending` and `next`, or give them both
+    /// back to yield `pending`.
+    fn coalesce_pair(&mut self, pending: T, next: T) -> Result<T, (T, T)>;
+}
+
+/// Merges equal elements, for `dedup`.
+#[derive(Copy, Clone, Debug)]
+pub struct DedupEq;
+
+/// Merges elements the wrapped closure says are the same, for `dedup_by`.
+#[derive(Copy, Clone)]
+pub struct DedupPred<F>(F);
+
+/// Merges elements with equal keys, for `dedup_by_key`.
+#[derive(Copy, Clone)]
+pub struct DedupKey<F>(F);
+
+/// Merges equal elements and adds up their counts, for
+/// `dedup_with_count`.
+#[derive(Copy, Clone, Debug)]
+pub struct DedupCount;
+
+pub type Dedup<I> = Coalesce<I, DedupEq>;
+pub type DedupBy<I, F> = Coalesce<I, DedupPred<F>>;
+pub type DedupByKey<I, F> = Coalesce<I, DedupKey<F>>;
+pub type DedupWithCount<I> =
+    Coalesce<Map<I, fn(<I as Traversal>::Item) -> (usize, <I as Traversal>::Item)>, DedupCount>;
+
+fn run_of_one<T>(t: T) -> (usize, T) { (1, t) }
+
+#[derive(Copy, Clone)]
+pub struct GroupBy<I, F> {
+    iter: I,
+    key: F
+}
+
+#[derive(Copy, Clone)]
+pub struct Split<I, P> {
+    iter: I,
+    predicate: P
+}
+
+/// A step through the groups of a `group_by` or `split`, as passed to
+/// their `foreach_events`.
+///
+/// Every group is reported as a `Start`, then each of its elements, then
+/// an `End`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GroupEvent<K, T> {
+    /// A new group begins; for `group_by` this carries its key.
+    Start(K),
+    /// The next element of the current group.
+    Elem(T),
+    /// The current group is over.
+    End,
+}
+
+#[derive(Copy, Clone)]
+pub struct Intersperse<I, T> {
+    iter: I,
+    separator: T
+}
+
+#[derive(Copy, Clone)]
+pub struct IntersperseWith<I, F> {
+    iter: I,
+    separator: F
+}
+
+#[derive(Copy, Clone)]
+pub struct WithPosition<I> {
+    iter: I
+}
+
+/// Where an element of `with_position` falls in the traversal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// The first of several elements.
+    First,
+    /// Neither the first nor the last element.
+    Middle,
+    /// The last of several elements.
+    Last,
+    /// The one and only element.
+    Only,
+}
+
+/// Formats a traversal as its elements joined by a separator, created by
+/// `display_with`.
+#[derive(Copy, Clone)]
+pub struct DisplayWith<'a, I> {
+    iter: I,
+    separator: &'a str
+}
+
 #[derive(Copy, Clone)]
 pub struct Inspect<I, F> {
     iter: I,
@@ -198,13 +1151,165 @@ pub struct Chain<I, O> {
     two: O
 }
 
+#[derive(Copy, Clone)]
+pub struct Cycle<I> {
+    iter: I
+}
+
+#[derive(Copy, Clone)]
+pub struct CycleN<I> {
+    iter: I,
+    n: usize
+}
+
 #[derive(Copy, Clone)]
 pub struct FlatMap<I, F> {
     iter: I,
     producer: F
 }
 
+#[derive(Copy, Clone)]
+pub struct FlatMapIter<I, F> {
+    iter: I,
+    producer: F
+}
+
+#[derive(Copy, Clone)]
+pub struct Flatten<I> {
+    iter: I
+}
+
 #[derive(Copy, Clone)]
 pub struct Cloned<I> {
     iter: I,
 }
+
+#[derive(Copy, Clone)]
+pub struct Rev<I> {
+    iter: I,
+}
+
+#[derive(Copy, Clone)]
+pub struct StepBy<I> {
+    iter: I,
+    step: usize,
+}
+
+#[derive(Copy, Clone)]
+pub struct Zip<I, O> {
+    one: I,
+    two: O,
+}
+
+#[derive(Copy, Clone)]
+pub struct ZipIter<I, J> {
+    iter: I,
+    other: J,
+}
+
+#[derive(Copy, Clone)]
+pub struct ZipLongestIter<I, J> {
+    iter: I,
+    other: J,
+}
+
+#[derive(Copy, Clone)]
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+#[derive(Copy, Clone)]
+pub struct ChunksExact<I> {
+    iter: I,
+    size: usize,
+}
+
+#[derive(Copy, Clone)]
+pub struct BatchByWeight<I, F> {
+    iter: I,
+    max: usize,
+    weight: F,
+}
+
+#[derive(Copy, Clone)]
+pub struct TupleWindows<I, W> {
+    iter: I,
+    marker: PhantomData<fn() -> W>,
+}
+
+/// A tuple of elements that `tuple_windows` can yield.
+pub trait TupleWindow<T>: Clone {
+    /// How many elements the tuple holds.
+    fn size() -> usize;
+
+    /// Build the first window out of exactly `size()` elements.
+    fn from_vec(elems: Vec<T>) -> Self;
+
+    /// Move the window along by one, dropping its first element and
+    /// ending it with `next`.
+    fn shift(&mut self, next: T);
+}
+
+#[derive(Copy, Clone)]
+pub struct Windows<I> {
+    iter: I,
+    size: usize,
+}
+
+/// A borrowed window of elements, as passed to `Windows::foreach_view`.
+///
+/// The window lives in a ring buffer, so it is stored as two slices which
+/// together hold its elements in order.
+#[derive(Copy, Clone, Debug)]
+pub struct WindowView<'a, T: 'a> {
+    front: &'a [T],
+    back: &'a [T],
+}
+
+/// The outcome of `minmax`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MinMaxResult<T> {
+    /// The traversal was empty.
+    NoElements,
+    /// The traversal had exactly one element, which is both the least and
+    /// the greatest.
+    OneElement(T),
+    /// The least and the greatest of two or more elements.
+    MinMax(T, T),
+}
+
+impl<T: Clone> MinMaxResult<T> {
+    /// Return the least and greatest elements as a pair, cloning the only
+    /// element if there is just one.
+    pub fn into_option(self) -> Option<(T, T)> {
+        match self {
+            MinMaxResult::NoElements => None,
+            MinMaxResult::OneElement(t) => Some((t.clone(), t)),
+            MinMaxResult::MinMax(min, max) => Some((min, max))
+        }
+    }
+}
+
+/// An element of `zip_longest_iter`: a pair while both sides last, then
+/// whatever is left of the longer one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EitherOrBoth<A, B> {
+    /// Both sides still had an element.
+    Both(A, B),
+    /// Only the traversal had an element left.
+    Left(A),
+    /// Only the iterator had an element left.
+    Right(B),
+}
+
+/// An `Iterator` over the elements of a traversal running on another
+/// thread, created by `spawn_iter`.
+///
+/// Dropping it joins the thread, which blocks until the traversal next
+/// hands over an element or ends.
+pub struct SpawnIter<T> {
+    rx: mpsc::Receiver<T>,
+    producer: Option<thread::JoinHandle<()>>,
+    hint: (usize, Option<usize>),
+}
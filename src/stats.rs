@@ -0,0 +1,618 @@
+//! Single-pass statistics over numeric traversals.
+//!
+//! Import `Statistics` to get the consumers on any traversal of numbers.
+//! `Summary` and `Histogram` can also be filled by hand and merged, so
+//! results computed over separate parts of the data can be combined.
+//!
+//! NaNs are not numbers to be summarized, so every type and consumer here
+//! skips them: they are not counted and take no part in any result.
+
+use std::f64;
+use {Traversal, IntoTraversal, FromTraversal};
+
+use num::traits::ToPrimitive;
+
+/// Count, mean, variance and extremes of a stream of numbers, updated one
+/// value at a time with Welford's algorithm.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Summary {
+    count: usize,
+    mean: f64,
+    /// The sum of squared differences from the mean.
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Summary {
+    /// Creates a summary of no values.
+    #[inline]
+    pub fn new() -> Summary {
+        Summary { count: 0, mean: 0.0, m2: 0.0, min: f64::INFINITY, max: f64::NEG_INFINITY }
+    }
+
+    /// Add one value to the summary. NaNs are skipped.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() { return }
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Fold another summary into this one, as if its values had been
+    /// pushed here.
+    pub fn merge(&mut self, other: &Summary) {
+        if other.count == 0 { return }
+        if self.count == 0 {
+            *self = *other;
+            return
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let (n, m) = (self.count as f64, other.count as f64);
+        self.mean += delta * m / count as f64;
+        self.m2 += other.m2 + delta * delta * n * m / count as f64;
+        self.count = count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// The number of values.
+    #[inline]
+    pub fn count(&self) -> usize { self.count }
+
+    /// The arithmetic mean, or `None` if there are no values.
+    #[inline]
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 { None } else { Some(self.mean) }
+    }
+
+    /// The population variance, or `None` if there are no values.
+    #[inline]
+    pub fn variance(&self) -> Option<f64> {
+        if self.count == 0 { None } else { Some(self.m2 / self.count as f64) }
+    }
+
+    /// The sample variance, or `None` if there are fewer than two values.
+    #[inline]
+    pub fn sample_variance(&self) -> Option<f64> {
+        if self.count < 2 { None } else { Some(self.m2 / (self.count - 1) as f64) }
+    }
+
+    /// The population standard deviation, or `None` if there are no
+    /// values.
+    #[inline]
+    pub fn stddev(&self) -> Option<f64> { self.variance().map(f64::sqrt) }
+
+    /// The sample standard deviation, or `None` if there are fewer than
+    /// two values.
+    #[inline]
+    pub fn sample_stddev(&self) -> Option<f64> { self.sample_variance().map(f64::sqrt) }
+
+    /// The least value, or `None` if there are no values.
+    #[inline]
+    pub fn min(&self) -> Option<f64> {
+        if self.count == 0 { None } else { Some(self.min) }
+    }
+
+    /// The greatest value, or `None` if there are no values.
+    #[inline]
+    pub fn max(&self) -> Option<f64> {
+        if self.count == 0 { None } else { Some(self.max) }
+    }
+}
+
+impl Default for Summary {
+    #[inline]
+    fn default() -> Summary { Summary::new() }
+}
+
+impl<T: ToPrimitive> FromTraversal<T> for Summary {
+    fn from_traversal<I: IntoTraversal<Item=T>>(traversable: I) -> Self {
+        let mut summary = Summary::new();
+        traversable.into_traversal().run(|t| summary.push(to_f64(t)));
+        summary
+    }
+}
+
+/// How a `Histogram` spaces its bins.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Scale {
+    /// Bins of equal width.
+    Linear,
+    /// Bins of equal width on a log scale, each a constant factor wider
+    /// than the one before.
+    Log,
+}
+
+/// Counts of values falling into bins between two bounds.
+///
+/// The bins cover `low` to `high` inclusive. Values outside the bounds are
+/// counted separately, and NaNs are ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    scale: Scale,
+    low: f64,
+    high: f64,
+    counts: Vec<usize>,
+    below: usize,
+    above: usize,
+}
+
+impl Histogram {
+    /// Creates a histogram of `bins` equally wide bins from `low` to
+    /// `high`.
+    ///
+    /// Panics if `bins` is zero or the bounds are not finite with
+    /// `low < high`.
+    pub fn linear(low: f64, high: f64, bins: usize) -> Histogram {
+        assert!(low.is_finite() && high.is_finite() && low < high,
+                "Histogram: bounds must be finite with low < high");
+        Histogram::with_scale(Scale::Linear, low, high, bins)
+    }
+
+    /// Creates a histogram of `bins` bins from `low` to `high` that are
+    /// equally wide on a log scale.
+    ///
+    /// Panics if `bins` is zero or the bounds are not finite with
+    /// `0 < low < high`.
+    pub fn log(low: f64, high: f64, bins: usize) -> Histogram {
+        assert!(high.is_finite() && 0.0 < low && low < high,
+                "Histogram: bounds must be finite with 0 < low < high");
+        Histogram::with_scale(Scale::Log, low, high, bins)
+    }
+
+    fn with_scale(scale: Scale, low: f64, high: f64, bins: usize) -> Histogram {
+        assert!(bins != 0, "Histogram: there must be at least one bin");
+        Histogram { scale: scale, low: low, high: high, counts: vec![0; bins],
+                    below: 0, above: 0 }
+    }
+
+    /// Count one value.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() { return }
+        if x < self.low {
+            self.below += 1;
+        } else if x > self.high {
+            self.above += 1;
+        } else {
+            let bins = self.counts.len();
+            // Halve before subtracting and take logs before dividing, so
+            // that bounds far apart don't overflow.
+            let position = match self.scale {
+                Scale::Linear => (x / 2.0 - self.low / 2.0) / (self.high / 2.0 - self.low / 2.0),
+                Scale::Log => (x.ln() - self.low.ln()) / (self.high.ln() - self.low.ln())
+            };
+            // The top edge belongs to the last bin.
+            let bin = ((position * bins as f64) as usize).min(bins - 1);
+            self.counts[bin] += 1;
+        }
+    }
+
+    /// Add the counts of another histogram with the same bins to this one.
+    ///
+    /// Panics if the bins differ.
+    pub fn merge(&mut self, other: &Histogram) {
+        assert!(self.scale == other.scale && self.low == other.low && self.high == other.high
+                && self.counts.len() == other.counts.len(),
+                "Histogram: can only merge histograms with the same bins");
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += *other;
+        }
+        self.below += other.below;
+        self.above += other.above;
+    }
+
+    /// How the bins are spaced.
+    #[inline]
+    pub fn scale(&self) -> Scale { self.scale }
+
+    /// The count in each bin, from lowest to highest.
+    #[inline]
+    pub fn counts(&self) -> &[usize] { &self.counts }
+
+    /// The lower and upper edge of bin `i`.
+    ///
+    /// Panics if there is no bin `i`.
+    pub fn bin_range(&self, i: usize) -> (f64, f64) {
+        let bins = self.counts.len();
+        assert!(i < bins, "Histogram: bin out of range");
+        let edge = |i: usize| {
+            let fraction = i as f64 / bins as f64;
+            let mix = |low: f64, high: f64| low * (1.0 - fraction) + high * fraction;
+            match self.scale {
+                Scale::Linear => mix(self.low, self.high),
+                Scale::Log => mix(self.low.ln(), self.high.ln()).exp()
+            }
+        };
+        // Compute the outer edges exactly.
+        let lower = if i == 0 { self.low } else { edge(i) };
+        let upper = if i + 1 == bins { self.high } else { edge(i + 1) };
+        (lower, upper)
+    }
+
+    /// The number of values below the lowest bin.
+    #[inline]
+    pub fn below(&self) -> usize { self.below }
+
+    /// The number of values above the highest bin.
+    #[inline]
+    pub fn above(&self) -> usize { self.above }
+
+    /// The number of values counted, in or out of the bins.
+    pub fn total(&self) -> usize {
+        self.counts.iter().fold(self.below + self.above, |total, &c| total + c)
+    }
+}
+
+/// Everything `describe` finds out about a traversal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Description {
+    /// Count, mean, variance and extremes.
+    pub summary: Summary,
+    /// The first quartile, median and third quartile, or `None` if there
+    /// were no values.
+    pub quartiles: Option<(f64, f64, f64)>,
+    /// A linear histogram spanning the values, or `None` if there were no
+    /// values or any of them was infinite, since bins can't span those.
+    pub histogram: Option<Histogram>,
+}
+
+/// Statistics consumers for traversals of numbers.
+///
+/// Every value is converted to `f64` first, and NaNs are skipped.
+pub trait Statistics: Traversal where Self::Item: ToPrimitive {
+    /// The count, mean, variance and extremes, in a single pass.
+    fn summary(self) -> Summary { self.collect() }
+
+    /// The arithmetic mean, or `None` if there are no values.
+    fn mean(self) -> Option<f64> { self.summary().mean() }
+
+    /// The population variance, or `None` if there are no values.
+    fn variance(self) -> Option<f64> { self.summary().variance() }
+
+    /// The population standard deviation, or `None` if there are no
+    /// values.
+    fn stddev(self) -> Option<f64> { self.summary().stddev() }
+
+    /// Count every value into `histogram` and return it.
+    fn histogram(self, mut histogram: Histogram) -> Histogram {
+        self.run(|t| histogram.push(to_f64(t)));
+        histogram
+    }
+
+    /// The exact quantile `q` of the values, interpolating linearly
+    /// between the two closest ranks, or `None` if there are no values.
+    ///
+    /// All the values are collected, then the ones needed are selected in
+    /// linear time. Panics if `q` is not between 0 and 1.
+    fn quantile(self, q: f64) -> Option<f64> {
+        check_quantile(q);
+        let mut values = collect_f64(self);
+        if values.is_empty() { return None }
+        Some(select_quantile(&mut values, q))
+    }
+
+    /// The median of the values, or `None` if there are no values.
+    fn median(self) -> Option<f64> { self.quantile(0.5) }
+
+    /// The exact quantiles `qs` of the values, in the same order as `qs`,
+    /// or `None` if there are no values.
+    ///
+    /// All the values are collected and sorted once. Panics if any of `qs`
+    /// is not between 0 and 1.
+    fn quantiles(self, qs: &[f64]) -> Option<Vec<f64>> {
+        for &q in qs { check_quantile(q); }
+        let mut values = collect_f64(self);
+        if values.is_empty() { return None }
+        values.sort_by(f64::total_cmp);
+        Some(qs.iter().map(|&q| sorted_quantile(&values, q)).collect())
+    }
+
+    /// Summarize the values, their quartiles and a histogram of `bins`
+    /// bins spanning them, all from a single pass.
+    ///
+    /// Panics if `bins` is zero.
+    fn describe(self, bins: usize) -> Description {
+        assert!(bins != 0, "describe: there must be at least one bin");
+        let mut summary = Summary::new();
+        let mut values = Vec::with_capacity(self.size_hint().0);
+        self.run(|t| {
+            let x = to_f64(t);
+            if x.is_nan() { return }
+            summary.push(x);
+            values.push(x);
+        });
+
+        if values.is_empty() {
+            return Description { summary: summary, quartiles: None, histogram: None }
+        }
+        values.sort_by(f64::total_cmp);
+        let quartiles = (sorted_quantile(&values, 0.25),
+                         sorted_quantile(&values, 0.5),
+                         sorted_quantile(&values, 0.75));
+
+        let histogram = match (summary.min(), summary.max()) {
+            (Some(min), Some(max)) if min.is_finite() && max.is_finite() => {
+
+                // Widen a single point into a bin around it, by enough to
+                // survive rounding at its magnitude.
+                let (low, high) = if min < max {
+                    (min, max)
+                } else {
+                    let pad = min.abs().max(1.0) * 0.5;
+                    ((min - pad).max(-f64::MAX), (max + pad).min(f64::MAX))
+                };
+                let mut histogram = Histogram::linear(low, high, bins);
+                for &x in &values { histogram.push(x); }
+                Some(histogram)
+            },
+            _ => None
+        };
+
+        Description { summary: summary, quartiles: Some(quartiles), histogram: histogram }
+    }
+}
+
+impl<T: Traversal> Statistics for T where T::Item: ToPrimitive {}
+
+#[inline]
+fn to_f64<T: ToPrimitive>(t: T) -> f64 {
+    t.to_f64().expect("stats: value cannot be represented as an f64")
+}
+
+fn collect_f64<T: Traversal>(trav: T) -> Vec<f64> where T::Item: ToPrimitive {
+    let mut values = Vec::with_capacity(trav.size_hint().0);
+    trav.run(|t| {
+        let x = to_f64(t);
+        if !x.is_nan() { values.push(x); }
+    });
+    values
+}
+
+fn check_quantile(q: f64) {
+    assert!((0.0..=1.0).contains(&q), "quantile: q must be between 0 and 1");
+}
+
+/// The rank of quantile `q` among `len` values, split into the index below
+/// it and how far it is towards the next.
+fn rank(len: usize, q: f64) -> (usize, f64) {
+    let rank = q * (len - 1) as f64;
+    let below = rank.floor();
+    (below as usize, rank - below)
+}
+
+fn sorted_quantile(sorted: &[f64], q: f64) -> f64 {
+    let (i, fraction) = rank(sorted.len(), q);
+    if fraction == 0.0 { sorted[i] } else { interpolate(sorted[i], sorted[i + 1], fraction) }
+}
+
+fn select_quantile(values: &mut [f64], q: f64) -> f64 {
+    let (i, fraction) = rank(values.len(), q);
+    let (_, &mut below, above) = values.select_nth_unstable_by(i, f64::total_cmp);
+    if fraction == 0.0 { return below }
+
+    // The next rank up is the least of everything above.
+    let next = above.iter().cloned().fold(f64::INFINITY, f64::min);
+    interpolate(below, next, fraction)
+}
+
+/// The point `fraction` of the way from `below` to `above`. Equal ends,
+/// infinite ones included, give that value, and ends too far apart for
+/// their difference to be finite are mixed instead.
+fn interpolate(below: f64, above: f64, fraction: f64) -> f64 {
+    if below == above { return below }
+    let gap = above - below;
+    if gap.is_finite() {
+        below + gap * fraction
+    } else {
+        below * (1.0 - fraction) + above * fraction
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use utils::{range, range_inclusive, linspace};
+    use Internal;
+
+    fn close(a: f64, b: f64) -> bool { (a - b).abs() < 1e-9 }
+
+    #[test]
+    fn test_summary() {
+        let summary = Internal::new(vec![2, 4, 4, 4, 5, 5, 7, 9]).summary();
+        assert_eq!(summary.count(), 8);
+        assert_eq!(summary.mean(), Some(5.0));
+        assert_eq!(summary.variance(), Some(4.0));
+        assert_eq!(summary.stddev(), Some(2.0));
+        assert!(close(summary.sample_variance().unwrap(), 32.0 / 7.0));
+        assert_eq!(summary.min(), Some(2.0));
+        assert_eq!(summary.max(), Some(9.0));
+
+        let empty = range(0, 0).summary();
+        assert_eq!(empty.count(), 0);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.min(), None);
+        assert_eq!(range(0, 1).summary().sample_variance(), None);
+
+        assert_eq!(range_inclusive(1u8, 255).mean(), Some(128.0));
+        assert_eq!(linspace(-1.0, 1.0, 101).mean().map(f64::abs).map(|m| m < 1e-12), Some(true));
+    }
+
+    #[test]
+    fn test_summary_stable() {
+        // Welford keeps its accuracy where the naive sum of squares cancels.
+        let summary = Internal::new(vec![1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0])
+            .summary();
+        assert_eq!(summary.sample_variance(), Some(30.0));
+    }
+
+    #[test]
+    fn test_summary_merge() {
+        let whole = range(0, 100).map(|x| (x * x) % 17).summary();
+        let mut left = range(0, 40).map(|x| (x * x) % 17).summary();
+        let right = range(40, 100).map(|x| (x * x) % 17).summary();
+        left.merge(&right);
+        assert_eq!(left.count(), whole.count());
+        assert!(close(left.mean().unwrap(), whole.mean().unwrap()));
+        assert!(close(left.variance().unwrap(), whole.variance().unwrap()));
+        assert_eq!(left.min(), whole.min());
+        assert_eq!(left.max(), whole.max());
+
+        let mut empty = Summary::new();
+        empty.merge(&whole);
+        assert_eq!(empty, whole);
+        let mut copy = whole;
+        copy.merge(&Summary::default());
+        assert_eq!(copy, whole);
+    }
+
+    #[test]
+    fn test_histogram_linear() {
+        let h = range(-2, 12).histogram(Histogram::linear(0.0, 10.0, 5));
+        assert_eq!(h.counts(), &[2, 2, 2, 2, 3]);
+        assert_eq!(h.below(), 2);
+        assert_eq!(h.above(), 1);
+        assert_eq!(h.total(), 14);
+        assert_eq!(h.bin_range(0), (0.0, 2.0));
+        assert_eq!(h.bin_range(4), (8.0, 10.0));
+
+        let mut wide = Histogram::linear(-1.7e308, 1.7e308, 4);
+        for &x in &[-1.7e308, -1e308, -1e307, 1e307, 1e308, 1.7e308] { wide.push(x); }
+        assert_eq!(wide.counts(), &[2, 1, 1, 2]);
+        let (low, high) = wide.bin_range(1);
+        assert!(close(low / 8.5e307, -1.0) && high == 0.0);
+
+        let mut nan = Histogram::linear(0.0, 1.0, 2);
+        nan.push(f64::NAN);
+        assert_eq!(nan.total(), 0);
+    }
+
+    #[test]
+    fn test_histogram_log() {
+        let h = Internal::new(vec![0.5, 1.0, 5.0, 10.0, 50.0, 99.0, 100.0, 1000.0])
+            .histogram(Histogram::log(1.0, 100.0, 2));
+        assert_eq!(h.counts(), &[2, 4]);
+        assert_eq!((h.below(), h.above()), (1, 1));
+        assert_eq!(h.scale(), Scale::Log);
+        let (low, high) = h.bin_range(0);
+        assert_eq!(low, 1.0);
+        assert!(close(high, 10.0));
+
+        let mut wide = Histogram::log(1e-300, 1e300, 2);
+        for &x in &[1e-300, 1e-10, 1e10, 1e300] { wide.push(x); }
+        assert_eq!(wide.counts(), &[2, 2]);
+        assert!(close(wide.bin_range(0).1, 1.0));
+    }
+
+    #[test]
+    fn test_histogram_merge() {
+        let mut h = range(0, 5).histogram(Histogram::linear(0.0, 10.0, 2));
+        h.merge(&range(5, 20).histogram(Histogram::linear(0.0, 10.0, 2)));
+        assert_eq!(h, range(0, 20).histogram(Histogram::linear(0.0, 10.0, 2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "same bins")]
+    fn test_histogram_merge_mismatch() {
+        let mut h = Histogram::linear(0.0, 10.0, 2);
+        h.merge(&Histogram::linear(0.0, 10.0, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "0 < low < high")]
+    fn test_histogram_log_bounds() {
+        Histogram::log(0.0, 10.0, 3);
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let data = vec![7, 1, 3, 9, 5];
+        assert_eq!(Internal::new(data.clone()).median(), Some(5.0));
+        assert_eq!(Internal::new(data.clone()).quantile(0.0), Some(1.0));
+        assert_eq!(Internal::new(data.clone()).quantile(1.0), Some(9.0));
+        assert_eq!(Internal::new(data.clone()).quantile(0.375), Some(4.0));
+        assert_eq!(Internal::new(data).quantiles(&[0.5, 0.0, 0.375, 1.0]),
+                   Some(vec![5.0, 1.0, 4.0, 9.0]));
+        assert_eq!(range(0, 4).median(), Some(1.5));
+        assert_eq!(range(0, 0).median(), None);
+        assert_eq!(range(0, 0).quantiles(&[0.5]), None);
+
+        // Selecting agrees with sorting.
+        for i in 0..=20 {
+            let q = i as f64 / 20.0;
+            let data = || range(0, 37).map(|x| (x * 23) % 37);
+            assert_eq!(data().quantile(q), data().quantiles(&[q]).map(|qs| qs[0]));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "between 0 and 1")]
+    fn test_quantile_range() {
+        range(0, 10).quantile(1.5);
+    }
+
+    #[test]
+    fn test_describe() {
+        let d = range(0, 101).describe(4);
+        assert_eq!(d.summary.count(), 101);
+        assert_eq!(d.summary.mean(), Some(50.0));
+        assert_eq!(d.quartiles, Some((25.0, 50.0, 75.0)));
+        let histogram = d.histogram.unwrap();
+        assert_eq!(histogram.counts(), &[25, 25, 25, 26]);
+        assert_eq!(histogram.total(), 101);
+
+        let single = range(3, 4).describe(3);
+        assert_eq!(single.quartiles, Some((3.0, 3.0, 3.0)));
+        assert_eq!(single.histogram.unwrap().counts(), &[0, 1, 0]);
+        for &x in &[1e20, -1e20, f64::MAX] {
+            let big = Internal::new(vec![x, x]).describe(3);
+            assert_eq!(big.quartiles, Some((x, x, x)));
+            assert_eq!(big.histogram.unwrap().total(), 2);
+        }
+        let big = Internal::new(vec![1e20]).describe(3);
+        assert_eq!(big.histogram.unwrap().total(), 1);
+
+        // Infinities have quartiles but no histogram.
+        let inf = Internal::new(vec![1.0, f64::INFINITY]).describe(3);
+        assert_eq!(inf.summary.count(), 2);
+        assert_eq!(inf.quartiles, Some((f64::INFINITY, f64::INFINITY, f64::INFINITY)));
+        assert_eq!(inf.histogram, None);
+        let inf = Internal::new(vec![f64::NEG_INFINITY, 1.0, 2.0, 3.0, f64::INFINITY]).describe(3);
+        assert_eq!(inf.quartiles, Some((1.0, 2.0, 3.0)));
+        let inf = Internal::new(vec![f64::INFINITY, f64::INFINITY]).describe(3);
+        assert_eq!(inf.quartiles, Some((f64::INFINITY, f64::INFINITY, f64::INFINITY)));
+        assert_eq!(Internal::new(vec![-f64::MAX, f64::MAX]).median(), Some(0.0));
+
+        let empty = range(0, 0).describe(3);
+        assert_eq!(empty.summary.count(), 0);
+        assert_eq!(empty.quartiles, None);
+        assert_eq!(empty.histogram, None);
+    }
+
+    #[test]
+    fn test_nan_skipped() {
+        let data = || Internal::new(vec![3.0, f64::NAN, 1.0, 2.0, f64::NAN]);
+        let summary = data().summary();
+        assert_eq!(summary.count(), 3);
+        assert_eq!(summary.mean(), Some(2.0));
+        assert_eq!((summary.min(), summary.max()), (Some(1.0), Some(3.0)));
+        assert_eq!(data().median(), Some(2.0));
+        assert_eq!(data().quantiles(&[0.0, 1.0]), Some(vec![1.0, 3.0]));
+        assert_eq!(data().histogram(Histogram::linear(0.0, 4.0, 2)).total(), 3);
+
+        let d = data().describe(2);
+        assert_eq!(d.summary, summary);
+        assert_eq!(d.quartiles, Some((1.5, 2.0, 2.5)));
+        assert_eq!(d.histogram.unwrap().total(), 3);
+
+        let only_nan = || Internal::new(vec![f64::NAN]);
+        assert_eq!(only_nan().summary().count(), 0);
+        assert_eq!(only_nan().median(), None);
+        assert_eq!(only_nan().describe(2).quartiles, None);
+    }
+}
@@ -1,10 +1,15 @@
-use Traversal;
+use {Traversal, DoubleEndedTraversal, ExactSizeTraversal, IndexedTraversal, Status};
+use {walk_strided, trusted};
 use std::ops::Add;
 
-use num::traits::PrimInt;
+use num::traits::{Zero, CheckedAdd, WrappingAdd, Saturating, Float, NumCast};
 
 /// An infinite iterator starting at `start` and advancing by `step` with each
 /// iteration
+///
+/// Stepping past the bounds of `A` behaves like `+` does: it panics in debug
+/// builds. Use `count_checked`, `count_wrapping` or `count_saturating` to
+/// choose what happens instead.
 #[derive(Copy, Clone)]
 pub struct Counter<A> {
     /// The current state the counter is at (next value to be yielded)
@@ -23,15 +28,142 @@ impl<A: Add<Output=A> + Clone> Traversal for Counter<A> {
     type Item = A;
 
     #[inline]
-    fn foreach<F>(self, mut f: F) where F: FnMut(A) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
         let mut i = self.start;
         loop {
-            let old = i;
-            // This is what std does, so I guess it's legit...
-            i = old.clone() + self.step.clone();
-            if f(old) { return; }
+            // Only step once the next value is actually wanted, so the last
+            // representable value can be yielded.
+            if f(i.clone()) { return Status::Stopped; }
+            i = i + self.step.clone();
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
+}
+
+/// A counter that stops instead of overflowing.
+#[derive(Copy, Clone)]
+pub struct CheckedCounter<A> {
+    start: A,
+    step: A,
+}
+
+/// Creates a new counter that ends after the last value before `start`
+/// stepped by `step` would overflow.
+#[inline]
+pub fn count_checked<A: CheckedAdd>(start: A, step: A) -> CheckedCounter<A> {
+    CheckedCounter { start: start, step: step }
+}
+
+impl<A: CheckedAdd + Clone> Traversal for CheckedCounter<A> {
+    type Item = A;
+
+    #[inline]
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        let mut i = self.start;
+        loop {
+            if f(i.clone()) { return Status::Stopped; }
+            i = match i.checked_add(&self.step) { None => return Status::Done, Some(x) => x };
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (1, None) }
+}
+
+/// A counter that wraps around at the bounds of its type.
+#[derive(Copy, Clone)]
+pub struct WrappingCounter<A> {
+    start: A,
+    step: A,
+}
+
+/// Creates a new infinite counter that wraps around on overflow.
+#[inline]
+pub fn count_wrapping<A: WrappingAdd>(start: A, step: A) -> WrappingCounter<A> {
+    WrappingCounter { start: start, step: step }
+}
+
+impl<A: WrappingAdd + Clone> Traversal for WrappingCounter<A> {
+    type Item = A;
+
+    #[inline]
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        let mut i = self.start;
+        loop {
+            if f(i.clone()) { return Status::Stopped; }
+            i = i.wrapping_add(&self.step);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
+}
+
+/// A counter that sticks at the bounds of its type.
+#[derive(Copy, Clone)]
+pub struct SaturatingCounter<A> {
+    start: A,
+    step: A,
+}
+
+/// Creates a new infinite counter that repeats the bound of its type once
+/// it is reached.
+#[inline]
+pub fn count_saturating<A: Saturating>(start: A, step: A) -> SaturatingCounter<A> {
+    SaturatingCounter { start: start, step: step }
+}
+
+impl<A: Saturating + Clone> Traversal for SaturatingCounter<A> {
+    type Item = A;
+
+    #[inline]
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        let mut i = self.start;
+        loop {
+            if f(i.clone()) { return Status::Stopped; }
+            i = i.saturating_add(self.step.clone());
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
+}
+
+/// Types that ranges can be taken over: values with a successor, a
+/// predecessor and a distance between them.
+///
+/// This is implemented for all the primitive integers and for `char`;
+/// implement it for your own index types to use them with `range` and
+/// `range_inclusive`.
+pub trait Step: Clone + PartialOrd {
+    /// The number of successor steps from `start` to `end`, zero if `end`
+    /// is not after `start`, or `None` if that does not fit in a `usize`.
+    fn steps_between(start: &Self, end: &Self) -> Option<usize>;
+
+    /// The value `n` steps after this one, or `None` if there is none.
+    fn forward(self, n: usize) -> Option<Self>;
+
+    /// The value `n` steps before this one, or `None` if there is none.
+    fn backward(self, n: usize) -> Option<Self>;
+
+    /// The value right after this one, or `None` if there is none.
+    #[inline]
+    fn successor(self) -> Option<Self> { self.forward(1) }
+
+    /// The value right before this one, or `None` if there is none.
+    #[inline]
+    fn predecessor(self) -> Option<Self> { self.backward(1) }
+}
+
+/// An exact size hint, or an unbounded one if the size overflowed.
+#[inline]
+fn exact(len: Option<usize>) -> (usize, Option<usize>) {
+    match len {
+        Some(n) => (n, Some(n)),
+        None => (usize::MAX, None)
+    }
 }
 
 /// An iterator over the range [start, stop)
@@ -44,22 +176,52 @@ pub struct Range<A> {
 /// Returns an iterator over the given range [start, stop) (that is, starting
 /// at start (inclusive), and ending at stop (exclusive)).
 #[inline]
-pub fn range<A: PrimInt>(start: A, stop: A) -> Range<A> {
+pub fn range<A: Step>(start: A, stop: A) -> Range<A> {
     Range { start: start, stop: stop }
 }
 
-// FIXME: rust-lang/rust#10414: Unfortunate type bound
-impl<A: PrimInt> Traversal for Range<A> {
+impl<A: Step> Traversal for Range<A> {
     type Item = A;
 
     #[inline]
-    fn foreach<F>(self, mut f: F) where F: FnMut(A) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
         let mut i = self.start;
-        let one = A::one();
         while i < self.stop {
-            let old = i;
-            i = old + one;
-            if f(old) { return; }
+            if f(i.clone()) { return Status::Stopped; }
+            i = match i.successor() { None => return Status::Done, Some(x) => x }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(A) -> bool {
+        let mut i = match self.start.forward(start) { None => return Status::Done, Some(x) => x };
+        while i < self.stop {
+            if f(i.clone()) { return Status::Stopped; }
+            i = match i.forward(step) { None => return Status::Done, Some(x) => x }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact(A::steps_between(&self.start, &self.stop))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<A: Step> DoubleEndedTraversal for Range<A> {
+    #[inline]
+    fn foreach_rev<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        if self.start >= self.stop { return Status::Done; }
+        let mut i = match self.stop.predecessor() { None => return Status::Done, Some(x) => x };
+        loop {
+            if f(i.clone()) { return Status::Stopped; }
+            if i <= self.start { return Status::Done; }
+            i = match i.predecessor() { None => return Status::Done, Some(x) => x }
         }
     }
 }
@@ -73,23 +235,78 @@ pub struct RangeInclusive<A> {
 
 /// Return an iterator over the range [start, stop]
 #[inline]
-pub fn range_inclusive<A: PrimInt>(start: A, stop: A) -> RangeInclusive<A> {
+pub fn range_inclusive<A: Step>(start: A, stop: A) -> RangeInclusive<A> {
     RangeInclusive { start: start, stop: stop }
 }
 
-impl<A: PrimInt> Traversal for RangeInclusive<A> {
+impl<A: Step> Traversal for RangeInclusive<A> {
     type Item = A;
 
     #[inline]
-    fn foreach<F>(self, mut f: F) where F: FnMut(A) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        if self.start > self.stop { return Status::Done; }
         let mut i = self.start;
-        let one = A::one();
+        // Check for the end before stepping, since stop has no successor
+        // at the end of the type.
+        loop {
+            if f(i.clone()) { return Status::Stopped; }
+            if i == self.stop { return Status::Done; }
+            i = match i.successor() { None => return Status::Done, Some(x) => x }
+        }
+    }
+
+    #[inline]
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(A) -> bool {
+        let mut i = match self.start.forward(start) { None => return Status::Done, Some(x) => x };
+        // Jumping by step never needs a value past the end of the type.
         while i <= self.stop {
-            let old = i;
-            i = old + one;
-            if f(old) { return; }
+            if f(i.clone()) { return Status::Stopped; }
+            i = match i.forward(step) { None => return Status::Done, Some(x) => x }
         }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.start > self.stop { return (0, Some(0)) }
+        exact(A::steps_between(&self.start, &self.stop).and_then(|n| n.checked_add(1)))
     }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<A: Step> DoubleEndedTraversal for RangeInclusive<A> {
+    #[inline]
+    fn foreach_rev<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        if self.start > self.stop { return Status::Done; }
+        let mut i = self.stop;
+        loop {
+            if f(i.clone()) { return Status::Stopped; }
+            if i == self.start { return Status::Done; }
+            i = match i.predecessor() { None => return Status::Done, Some(x) => x }
+        }
+    }
+}
+
+// Only the types whose ranges always fit in a usize, as in std.
+macro_rules! indexed_range {
+    ($($range:ident: $($t:ty)*;)*) => {$($(
+        impl ExactSizeTraversal for $range<$t> {}
+
+        impl IndexedTraversal for $range<$t> {
+            #[inline]
+            fn get(&mut self, index: usize) -> $t {
+                self.start.forward(index).expect("index out of range")
+            }
+        }
+    )*)*}
+}
+
+indexed_range! {
+    Range: u8 u16 u32 usize i8 i16 i32 isize;
+    RangeInclusive: u8 u16 i8 i16;
 }
 
 /// An iterator over the range [start, stop) by `step`. It handles overflow by stopping.
@@ -102,32 +319,82 @@ pub struct RangeStep<A> {
 
 /// Return an iterator over the range [start, stop) by `step`. It handles overflow by stopping.
 #[inline]
-pub fn range_step<A: PrimInt>(start: A, stop: A, step: A) -> RangeStep<A> {
+pub fn range_step<A: Step + Zero + CheckedAdd>(start: A, stop: A, step: A) -> RangeStep<A> {
     RangeStep { start: start, stop: stop, step: step }
 }
 
-impl<A: PrimInt> Traversal for RangeStep<A> {
+impl<A: Step + Zero + CheckedAdd> Traversal for RangeStep<A> {
     type Item = A;
 
     #[inline]
-    fn foreach<F>(self, mut f: F) where F: FnMut(A) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
         let mut i = self.start;
         // branch once and duplicate trivial logic for the perf
         if self.step > A::zero() {
             while i < self.stop {
-                let old = i;
                 let temp = i.checked_add(&self.step);
-                if f(old) { return; }
-                i = match temp { None => return, Some(x) => x }
+                if f(i) { return Status::Stopped; }
+                i = match temp { None => return Status::Done, Some(x) => x }
             }
         } else {
             while i > self.stop {
-                let old = i;
                 let temp = i.checked_add(&self.step);
-                if f(old) { return; }
-                i = match temp { None => return, Some(x) => x }
+                if f(i) { return Status::Stopped; }
+                i = match temp { None => return Status::Done, Some(x) => x }
             }
         }
+        Status::Done
+    }
+
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(A) -> bool {
+        let (forward, first, stride) = match stride_offsets(&self.step, start, step) {
+            Some(offsets) => offsets,
+            None => return walk_strided(self, start, step, f)
+        };
+
+        // branch once and duplicate trivial logic for the perf
+        if forward {
+            let mut i = match self.start.forward(first) { None => return Status::Done, Some(x) => x };
+            while i < self.stop {
+                if f(i.clone()) { return Status::Stopped; }
+                i = match i.forward(stride) { None => return Status::Done, Some(x) => x }
+            }
+        } else {
+            let mut i = match self.start.backward(first) { None => return Status::Done, Some(x) => x };
+            while i > self.stop {
+                if f(i.clone()) { return Status::Stopped; }
+                i = match i.backward(stride) { None => return Status::Done, Some(x) => x }
+            }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let zero = A::zero();
+        let (distance, step) = if self.step > zero {
+            (A::steps_between(&self.start, &self.stop), A::steps_between(&zero, &self.step))
+        } else if self.step < zero {
+            (A::steps_between(&self.stop, &self.start), A::steps_between(&self.step, &zero))
+        } else if self.start > self.stop {
+            return (usize::MAX, None)
+        } else {
+            return (0, Some(0))
+        };
+
+        exact(distance.and_then(|n| step.map(|s| n / s + if n % s != 0 { 1 } else { 0 })))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<A: Step + Zero + CheckedAdd> DoubleEndedTraversal for RangeStep<A> {
+    #[inline]
+    fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(A) -> bool {
+        let len = self.size_hint().1;
+        step_rev(self.start, self.step, len, f)
     }
 }
 
@@ -141,35 +408,491 @@ pub struct RangeStepInclusive<A> {
 
 /// Return an iterator over the range [start, stop] by `step`. It handles overflow by stopping.
 #[inline]
-pub fn range_step_inclusive<A: PrimInt>(start: A, stop: A, step: A) -> RangeStepInclusive<A> {
+pub fn range_step_inclusive<A>(start: A, stop: A, step: A) -> RangeStepInclusive<A>
+where A: Step + Zero + CheckedAdd {
     RangeStepInclusive { start: start, stop: stop, step: step }
 }
 
-impl<A: PrimInt> Traversal for RangeStepInclusive<A> {
+impl<A: Step + Zero + CheckedAdd> Traversal for RangeStepInclusive<A> {
     type Item = A;
 
     #[inline]
-    fn foreach<F>(self, mut f: F) where F: FnMut(A) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
         let mut i = self.start;
         // branch once and duplicate trivial logic for the perf
         if self.step > A::zero() {
             while i <= self.stop {
-                let old = i;
                 let temp = i.checked_add(&self.step);
-                if f(old) { return; }
-                i = match temp { None => return, Some(x) => x }
+                if f(i) { return Status::Stopped; }
+                i = match temp { None => return Status::Done, Some(x) => x }
             }
         } else {
             while i >= self.stop {
-                let old = i;
                 let temp = i.checked_add(&self.step);
-                if f(old) { return; }
-                i = match temp { None => return, Some(x) => x }
+                if f(i) { return Status::Stopped; }
+                i = match temp { None => return Status::Done, Some(x) => x }
+            }
+        }
+        Status::Done
+    }
+
+    fn foreach_strided<F>(self, start: usize, step: usize, mut f: F) -> Status
+    where F: FnMut(A) -> bool {
+        let (forward, first, stride) = match stride_offsets(&self.step, start, step) {
+            Some(offsets) => offsets,
+            None => return walk_strided(self, start, step, f)
+        };
+
+        // branch once and duplicate trivial logic for the perf
+        if forward {
+            let mut i = match self.start.forward(first) { None => return Status::Done, Some(x) => x };
+            while i <= self.stop {
+                if f(i.clone()) { return Status::Stopped; }
+                i = match i.forward(stride) { None => return Status::Done, Some(x) => x }
             }
+        } else {
+            let mut i = match self.start.backward(first) { None => return Status::Done, Some(x) => x };
+            while i >= self.stop {
+                if f(i.clone()) { return Status::Stopped; }
+                i = match i.backward(stride) { None => return Status::Done, Some(x) => x }
+            }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let zero = A::zero();
+        let (distance, step) = if self.step > zero {
+            if self.start > self.stop { return (0, Some(0)) }
+            (A::steps_between(&self.start, &self.stop), A::steps_between(&zero, &self.step))
+        } else if self.step < zero {
+            if self.start < self.stop { return (0, Some(0)) }
+            (A::steps_between(&self.stop, &self.start), A::steps_between(&self.step, &zero))
+        } else if self.start >= self.stop {
+            return (usize::MAX, None)
+        } else {
+            return (0, Some(0))
+        };
+
+        exact(distance.and_then(|n| step.and_then(|s| (n / s).checked_add(1))))
+    }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<A: Step + Zero + CheckedAdd> DoubleEndedTraversal for RangeStepInclusive<A> {
+    #[inline]
+    fn foreach_rev<F>(self, f: F) -> Status where F: FnMut(A) -> bool {
+        let len = self.size_hint().1;
+        step_rev(self.start, self.step, len, f)
+    }
+}
+
+/// For a stepped range, whether it goes forwards and how far from its start
+/// the `start`th value is and the distance between every `stride`th value,
+/// or `None` if those don't fit in a usize or the step is zero.
+fn stride_offsets<A: Step + Zero>(step: &A, start: usize, stride: usize)
+                                  -> Option<(bool, usize, usize)> {
+    let zero = A::zero();
+    let (forward, by) = if *step > zero {
+        (true, A::steps_between(&zero, step))
+    } else if *step < zero {
+        (false, A::steps_between(step, &zero))
+    } else {
+        return None
+    };
+
+    by.and_then(|by| by.checked_mul(start).and_then(|first| {
+        by.checked_mul(stride).map(|stride| (forward, first, stride))
+    }))
+}
+
+/// Run the `len` values of a stepped range backwards, starting from the
+/// last one and stepping back towards `start`.
+fn step_rev<A, F>(start: A, step: A, len: Option<usize>, mut f: F) -> Status
+where A: Step + Zero, F: FnMut(A) -> bool {
+    let zero = A::zero();
+    if len == Some(0) { return Status::Done; }
+    // A zero step repeats start forever, which reads the same both ways.
+    if step == zero { loop { if f(start.clone()) { return Status::Stopped; } } }
+    let len = len.expect("stepped range is too long to reverse");
+
+    let forward = step > zero;
+    let by = if forward { A::steps_between(&zero, &step) } else { A::steps_between(&step, &zero) };
+    let by = by.expect("stepped range is too long to reverse");
+    // All the values were reachable going forwards, so this can't overflow.
+    let offset = (len - 1) * by;
+    let mut i = if forward { start.forward(offset) } else { start.backward(offset) }.unwrap();
+    for remaining in (0..len).rev() {
+        if f(i.clone()) { return Status::Stopped; }
+        if remaining == 0 { break; }
+        i = if forward { i.backward(by) } else { i.forward(by) }.unwrap();
+    }
+    Status::Done
+}
+
+// The same types as the plain ranges. A zero step repeats `start` forever,
+// so such a range has no exact length and `len` must not be asked for.
+macro_rules! indexed_range_step {
+    ($($range:ident: $($t:ty)*;)*) => {$($(
+        impl ExactSizeTraversal for $range<$t> {}
+
+        impl IndexedTraversal for $range<$t> {
+            #[inline]
+            fn get(&mut self, index: usize) -> $t {
+                match stride_offsets(&self.step, index, 1) {
+                    Some((true, by, _)) => self.start.forward(by),
+                    Some((false, by, _)) => self.start.backward(by),
+                    None => Some(self.start)
+                }.expect("index out of range")
+            }
+        }
+    )*)*}
+}
+
+indexed_range_step! {
+    RangeStep: u8 u16 u32 usize i8 i16 i32 isize;
+    RangeStepInclusive: u8 u16 i8 i16;
+}
+
+/// Converts an index to a float. This can't fail for the float types.
+#[inline]
+fn to_float<F: Float>(i: usize) -> F {
+    NumCast::from(i).unwrap()
+}
+
+/// An iterator over `len` evenly spaced floats from `start` to `end`,
+/// both included.
+#[derive(Copy, Clone)]
+pub struct Linspace<F> {
+    start: F,
+    end: F,
+    step: F,
+    len: usize,
+}
+
+/// Return an iterator over `n` evenly spaced values from `start` to `end`
+/// inclusive. Each value is computed from its index, so the error does not
+/// grow along the range, and the last value is exactly `end`.
+///
+/// Panics if `start` or `end` is NaN or infinite.
+#[inline]
+pub fn linspace<F: Float>(start: F, end: F, n: usize) -> Linspace<F> {
+    assert!(start.is_finite() && end.is_finite(),
+            "linspace: start and end must be finite");
+    let step = if n > 1 { (end - start) / to_float(n - 1) } else { F::zero() };
+    // A single point is the start, not the end.
+    let end = if n > 1 { end } else { start };
+    Linspace { start: start, end: end, step: step, len: n }
+}
+
+impl<F: Float> Linspace<F> {
+    /// The `i`th value, computed directly from the index.
+    #[inline]
+    fn at(&self, i: usize) -> F {
+        // Pin the last value to exactly end.
+        if i + 1 == self.len { return self.end }
+        let x = self.start + self.step * to_float(i);
+        if x.is_finite() { return x }
+
+        // The ends are too far apart for their difference to be a float,
+        // so mix them instead.
+        let t = to_float::<F>(i) / to_float(self.len - 1);
+        self.start * (F::one() - t) + self.end * t
+    }
+}
+
+impl<F: Float> Traversal for Linspace<F> {
+    type Item = F;
+
+    #[inline]
+    fn foreach<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        for i in 0..self.len {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn foreach_strided<G>(self, start: usize, step: usize, mut f: G) -> Status
+    where G: FnMut(F) -> bool {
+        for i in (start..self.len).step_by(step) {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (self.len, Some(self.len)) }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<F: Float> DoubleEndedTraversal for Linspace<F> {
+    #[inline]
+    fn foreach_rev<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        for i in (0..self.len).rev() {
+            if f(self.at(i)) { return Status::Stopped; }
         }
+        Status::Done
     }
 }
 
+impl<F: Float> ExactSizeTraversal for Linspace<F> {}
+
+impl<F: Float> IndexedTraversal for Linspace<F> {
+    #[inline]
+    fn get(&mut self, index: usize) -> F { self.at(index) }
+}
+
+/// An iterator over the floats from `start` up to but excluding `stop`,
+/// `step` apart.
+#[derive(Copy, Clone)]
+pub struct Arange<F> {
+    start: F,
+    step: F,
+    len: usize,
+}
+
+/// Return an iterator over `start`, `start + step`, `start + 2 * step`, ...
+/// for as long as the values are before `stop`. `step` may be negative.
+///
+/// Values are computed as `start + i * step` rather than by repeated
+/// addition, so they do not drift.
+///
+/// Panics if any argument is NaN or infinite, if `step` is zero, or if
+/// there are more than `usize::MAX` values.
+pub fn arange<F: Float>(start: F, stop: F, step: F) -> Arange<F> {
+    assert!(start.is_finite() && stop.is_finite() && step.is_finite(),
+            "arange: start, stop and step must be finite");
+    assert!(step != F::zero(), "arange: step must not be zero");
+
+    let mut steps = ((stop - start) / step).ceil();
+    if !steps.is_finite() {
+        // The span overflowed, so halve the bounds first.
+        let two = F::one() + F::one();
+        steps = ((stop / two - start / two) / step * two).ceil();
+    }
+    let len = if steps > F::zero() {
+        steps.to_usize().expect("arange: too many values for a usize")
+    } else {
+        0
+    };
+    let mut arange = Arange { start: start, step: step, len: len };
+
+    // Rounding in the division can make the count one too high.
+    if len > 0 {
+        let last = arange.at(len - 1);
+        if (step > F::zero() && last >= stop) || (step < F::zero() && last <= stop) {
+            arange.len -= 1;
+        }
+    }
+
+    arange
+}
+
+impl<F: Float> Arange<F> {
+    /// The `i`th value, computed directly from the index.
+    #[inline]
+    fn at(&self, i: usize) -> F {
+        let x = self.start + self.step * to_float(i);
+        if x.is_finite() { return x }
+
+        // Every value lies between two finite bounds, so only the offset
+        // overflowed; compute at half scale instead.
+        let two = F::one() + F::one();
+        (self.start / two + self.step / two * to_float(i)) * two
+    }
+}
+
+impl<F: Float> Traversal for Arange<F> {
+    type Item = F;
+
+    #[inline]
+    fn foreach<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        for i in 0..self.len {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn foreach_strided<G>(self, start: usize, step: usize, mut f: G) -> Status
+    where G: FnMut(F) -> bool {
+        for i in (start..self.len).step_by(step) {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (self.len, Some(self.len)) }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<F: Float> DoubleEndedTraversal for Arange<F> {
+    #[inline]
+    fn foreach_rev<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        for i in (0..self.len).rev() {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+}
+
+impl<F: Float> ExactSizeTraversal for Arange<F> {}
+
+impl<F: Float> IndexedTraversal for Arange<F> {
+    #[inline]
+    fn get(&mut self, index: usize) -> F { self.at(index) }
+}
+
+/// An iterator over `len` floats from `start` to `end`, both included,
+/// with a constant ratio between neighbours.
+#[derive(Copy, Clone)]
+pub struct Geomspace<F> {
+    start: F,
+    end: F,
+    log_step: F,
+    len: usize,
+}
+
+/// Return an iterator over `n` values from `start` to `end` inclusive,
+/// evenly spaced on a log scale. Both endpoints are exact.
+///
+/// Panics if `start` or `end` is NaN, infinite or zero, or if they have
+/// different signs.
+pub fn geomspace<F: Float>(start: F, end: F, n: usize) -> Geomspace<F> {
+    assert!(start.is_finite() && end.is_finite(),
+            "geomspace: start and end must be finite");
+    assert!(start != F::zero() && end != F::zero(),
+            "geomspace: start and end must not be zero");
+    assert!(start.is_sign_positive() == end.is_sign_positive(),
+            "geomspace: start and end must have the same sign");
+
+    let log_step = if n > 1 { (end / start).ln() / to_float(n - 1) } else { F::zero() };
+    let end = if n > 1 { end } else { start };
+    Geomspace { start: start, end: end, log_step: log_step, len: n }
+}
+
+impl<F: Float> Geomspace<F> {
+    /// The `i`th value, with both endpoints exact.
+    #[inline]
+    fn at(&self, i: usize) -> F {
+        if i == 0 {
+            self.start
+        } else if i + 1 == self.len {
+            self.end
+        } else {
+            self.start * (self.log_step * to_float(i)).exp()
+        }
+    }
+}
+
+impl<F: Float> Traversal for Geomspace<F> {
+    type Item = F;
+
+    #[inline]
+    fn foreach<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        for i in 0..self.len {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn foreach_strided<G>(self, start: usize, step: usize, mut f: G) -> Status
+    where G: FnMut(F) -> bool {
+        for i in (start..self.len).step_by(step) {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (self.len, Some(self.len)) }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<F: Float> DoubleEndedTraversal for Geomspace<F> {
+    #[inline]
+    fn foreach_rev<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        for i in (0..self.len).rev() {
+            if f(self.at(i)) { return Status::Stopped; }
+        }
+        Status::Done
+    }
+}
+
+impl<F: Float> ExactSizeTraversal for Geomspace<F> {}
+
+impl<F: Float> IndexedTraversal for Geomspace<F> {
+    #[inline]
+    fn get(&mut self, index: usize) -> F { self.at(index) }
+}
+
+/// An iterator over `base` raised to evenly spaced exponents.
+#[derive(Copy, Clone)]
+pub struct Logspace<F> {
+    exponents: Linspace<F>,
+    base: F,
+}
+
+/// Return an iterator over `n` values from `base^start` to `base^end`
+/// inclusive, with evenly spaced exponents.
+///
+/// Panics if any argument is NaN or infinite, or if `base` is not positive.
+pub fn logspace<F: Float>(start: F, end: F, n: usize, base: F) -> Logspace<F> {
+    assert!(base.is_finite() && base > F::zero(),
+            "logspace: base must be finite and positive");
+    Logspace { exponents: linspace(start, end, n), base: base }
+}
+
+impl<F: Float> Traversal for Logspace<F> {
+    type Item = F;
+
+    #[inline]
+    fn foreach<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        let base = self.base;
+        self.exponents.foreach(|e| f(base.powf(e)))
+    }
+
+    #[inline]
+    fn foreach_strided<G>(self, start: usize, step: usize, mut f: G) -> Status
+    where G: FnMut(F) -> bool {
+        let base = self.base;
+        self.exponents.foreach_strided(start, step, |e| f(base.powf(e)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.exponents.size_hint() }
+
+    #[inline]
+    fn trusted_len(&self) -> Option<usize> { trusted(self.size_hint()) }
+}
+
+impl<F: Float> DoubleEndedTraversal for Logspace<F> {
+    #[inline]
+    fn foreach_rev<G>(self, mut f: G) -> Status where G: FnMut(F) -> bool {
+        let base = self.base;
+        self.exponents.foreach_rev(|e| f(base.powf(e)))
+    }
+}
+
+impl<F: Float> ExactSizeTraversal for Logspace<F> {}
+
+impl<F: Float> IndexedTraversal for Logspace<F> {
+    #[inline]
+    fn get(&mut self, index: usize) -> F { self.base.powf(self.exponents.get(index)) }
+}
+
 /// Create a new iterator that endlessly repeats the element `elt`.
 #[inline]
 pub fn repeat<T: Clone>(elt: T) -> Repeat<T> {
@@ -186,11 +909,14 @@ impl<A: Clone> Traversal for Repeat<A> {
     type Item = A;
 
     #[inline]
-    fn foreach<F>(self, mut f: F) where F: FnMut(A) -> bool {
+    fn foreach<F>(self, mut f: F) -> Status where F: FnMut(A) -> bool {
         loop {
-            if f(self.element.clone()) { return; }
+            if f(self.element.clone()) { return Status::Stopped; }
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
 }
 
 /// An iterator that repeatedly applies a given function, starting
@@ -217,16 +943,18 @@ impl<A, I> Traversal for Iterate<A, I> where
     type Item = A;
 
     #[inline]
-    fn foreach<F>(mut self, mut f: F) where F: FnMut(A) -> bool {
-        if !f(self.seed.clone()) {
-            let mut cur = self.seed;
-            loop {
-                let next = (self.iter)(cur);
-                if f(next.clone()) { return; }
-                cur = next;
-            }
+    fn foreach<F>(mut self, mut f: F) -> Status where F: FnMut(A) -> bool {
+        if f(self.seed.clone()) { return Status::Stopped; }
+        let mut cur = self.seed;
+        loop {
+            let next = (self.iter)(cur);
+            if f(next.clone()) { return Status::Stopped; }
+            cur = next;
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
 }
 
 
@@ -234,7 +962,7 @@ impl<A, I> Traversal for Iterate<A, I> where
 #[cfg(test)]
 mod test {
     use super::*;
-    use Traversal;
+    use {Traversal, ExactSizeTraversal, IndexedTraversal, Status};
 
     #[test]
     fn test_range() {
@@ -273,6 +1001,335 @@ mod test {
     }
 
 
+    #[test]
+    fn test_status() {
+        assert_eq!(range(0, 5).foreach(|_| false), Status::Done);
+        assert_eq!(range(0, 5).foreach(|x| x == 4), Status::Stopped);
+        assert_eq!(range_inclusive(0, 5).foreach(|_| false), Status::Done);
+        assert_eq!(range_step(0u8, 255, 100).foreach(|_| false), Status::Done);
+        assert_eq!(range_step_inclusive(0, 5, 2).foreach(|x| x == 2), Status::Stopped);
+        assert_eq!(count(0, 1).foreach(|x| x == 2), Status::Stopped);
+        assert_eq!(repeat(0).foreach(|_| true), Status::Stopped);
+        assert_eq!(iterate(1, |x| x * 2).foreach(|x| x == 1), Status::Stopped);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        assert_eq!(range(0, 5).size_hint(), (5, Some(5)));
+        assert_eq!(range(5, 0).size_hint(), (0, Some(0)));
+        assert_eq!(range(-128i8, 127).size_hint(), (255, Some(255)));
+        assert_eq!(range(i64::MIN, i64::MAX).size_hint(), (usize::MAX, Some(usize::MAX)));
+        assert_eq!(range_inclusive(i64::MIN, i64::MAX).size_hint(), (usize::MAX, None));
+        assert_eq!(range_inclusive(0u8, 255).size_hint(), (256, Some(256)));
+        assert_eq!(range_inclusive(3, 2).size_hint(), (0, Some(0)));
+        assert_eq!(range_step(0, 20, 5).size_hint(), (4, Some(4)));
+        assert_eq!(range_step(20, 0, -6).size_hint(), (4, Some(4)));
+        assert_eq!(range_step(200u8, 255, 50).size_hint(), (2, Some(2)));
+        assert_eq!(range_step(-128i8, 127, -128).size_hint(), (0, Some(0)));
+        assert_eq!(range_step(127i8, -128, -128).size_hint(), (2, Some(2)));
+        assert_eq!(range_step(1, 0, 0).size_hint(), (usize::MAX, None));
+        assert_eq!(range_step_inclusive(0, 20, 5).size_hint(), (5, Some(5)));
+        assert_eq!(range_step_inclusive(20, 0, -6).size_hint(), (4, Some(4)));
+        assert_eq!(range_step_inclusive(200u8, 255, 50).size_hint(), (2, Some(2)));
+        assert_eq!(range_step_inclusive(0, 0, 0).size_hint(), (usize::MAX, None));
+        assert_eq!(count(0, 1).size_hint(), (usize::MAX, None));
+        assert_eq!(repeat(0).size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn test_size_hint_matches_count() {
+        for start in -10i8..10 {
+            for stop in -10i8..10 {
+                assert_eq!(range(start, stop).size_hint().0, range(start, stop).count());
+                assert_eq!(range_inclusive(start, stop).size_hint().0,
+                           range_inclusive(start, stop).count());
+                for step in (-4i8..5).filter(|&s| s != 0) {
+                    assert_eq!(range_step(start, stop, step).size_hint().0,
+                               range_step(start, stop, step).count());
+                    assert_eq!(range_step_inclusive(start, stop, step).size_hint().0,
+                               range_step_inclusive(start, stop, step).count());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(range(0u8, 10).len(), 10);
+        assert_eq!(range_inclusive(0u8, 255).len(), 256);
+    }
+
+    #[test]
+    fn test_range_extremes_u8() {
+        for start in 0..256 {
+            for stop in 0..256 {
+                let (start, stop) = (start as u8, stop as u8);
+                let expected: Vec<u8> = (start..stop).collect();
+                assert_eq!(range(start, stop).collect::<Vec<u8>>(), expected);
+                let expected: Vec<u8> = (start..=stop).collect();
+                assert_eq!(range_inclusive(start, stop).collect::<Vec<u8>>(), expected);
+            }
+        }
+        assert_eq!(range_inclusive(0u8, 255).last(), Some(255));
+        assert_eq!(range_inclusive(255u8, 255).collect::<Vec<u8>>(), vec![255]);
+    }
+
+    #[test]
+    fn test_range_extremes_i8() {
+        for start in -128..128 {
+            for stop in -128..128 {
+                let (start, stop) = (start as i8, stop as i8);
+                let expected: Vec<i8> = (start..stop).collect();
+                assert_eq!(range(start, stop).collect::<Vec<i8>>(), expected);
+                let expected: Vec<i8> = (start..=stop).collect();
+                assert_eq!(range_inclusive(start, stop).collect::<Vec<i8>>(), expected);
+            }
+        }
+        assert_eq!(range_inclusive(-128i8, 127).count(), 256);
+    }
+
+    #[test]
+    fn test_range_step_extremes() {
+        for step in 1..128 {
+            let expected: Vec<u8> = (0..=255u8).step_by(step as usize).collect();
+            assert_eq!(range_step_inclusive(0u8, 255, step).collect::<Vec<u8>>(), expected);
+            let expected: Vec<i8> = (-128..=127i8).step_by(step as usize).collect();
+            assert_eq!(range_step_inclusive(-128i8, 127, step as i8).collect::<Vec<i8>>(),
+                       expected);
+            let expected: Vec<i8> = (-128..=127i8).rev().step_by(step as usize).collect();
+            assert_eq!(range_step_inclusive(127i8, -128, -(step as i8)).collect::<Vec<i8>>(),
+                       expected);
+        }
+    }
+
+    #[test]
+    fn test_count_extremes() {
+        assert_eq!(count(250u8, 1).take(6).collect::<Vec<u8>>(), vec![250, 251, 252, 253, 254, 255]);
+        assert_eq!(count(-126i8, -1).take(3).collect::<Vec<i8>>(), vec![-126, -127, -128]);
+
+        assert_eq!(count_checked(250u8, 1).collect::<Vec<u8>>(), vec![250, 251, 252, 253, 254, 255]);
+        assert_eq!(count_checked(0u8, 100).collect::<Vec<u8>>(), vec![0, 100, 200]);
+        assert_eq!(count_checked(-100i8, -20).collect::<Vec<i8>>(), vec![-100, -120]);
+        assert_eq!(count_checked(0u8, 1).count(), 256);
+        assert_eq!(count_checked(-128i8, 1).count(), 256);
+        assert_eq!(count_checked(0u8, 1).foreach(|_| false), Status::Done);
+
+        assert_eq!(count_wrapping(254u8, 1).take(4).collect::<Vec<u8>>(), vec![254, 255, 0, 1]);
+        assert_eq!(count_wrapping(127i8, 1).take(2).collect::<Vec<i8>>(), vec![127, -128]);
+
+        assert_eq!(count_saturating(254u8, 1).take(4).collect::<Vec<u8>>(),
+                   vec![254, 255, 255, 255]);
+        assert_eq!(count_saturating(-127i8, -1).take(3).collect::<Vec<i8>>(),
+                   vec![-127, -128, -128]);
+    }
+
+    #[test]
+    fn test_range_char() {
+        assert_eq!(range('a', 'e').collect::<Vec<char>>(), vec!['a', 'b', 'c', 'd']);
+        assert_eq!(range_inclusive('x', 'z').collect::<Vec<char>>(), vec!['x', 'y', 'z']);
+        assert_eq!(range_inclusive('\u{D7FE}', '\u{E001}').collect::<Vec<char>>(),
+                   vec!['\u{D7FE}', '\u{D7FF}', '\u{E000}', '\u{E001}']);
+        assert_eq!(range_inclusive('\u{10FFFE}', char::MAX).count(), 2);
+        assert_eq!(range_inclusive('\0', char::MAX).size_hint().0,
+                   range_inclusive('\0', char::MAX).count());
+    }
+
+    #[test]
+    fn test_range_u128() {
+        let max = u128::MAX;
+        assert_eq!(range(max - 3, max).collect::<Vec<u128>>(), vec![max - 3, max - 2, max - 1]);
+        assert_eq!(range_inclusive(max - 1, max).collect::<Vec<u128>>(), vec![max - 1, max]);
+        assert_eq!(range(0, max).size_hint(), (usize::MAX, None));
+        assert_eq!(range_step(max - 10, max, 4).collect::<Vec<u128>>(),
+                   vec![max - 10, max - 6, max - 2]);
+    }
+
+    #[test]
+    fn test_linspace() {
+        assert_eq!(linspace(0.0, 1.0, 5).collect::<Vec<f64>>(), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(linspace(1.0, -1.0, 3).collect::<Vec<f32>>(), vec![1.0, 0.0, -1.0]);
+        assert_eq!(linspace(2.0, 5.0, 1).collect::<Vec<f64>>(), vec![2.0]);
+        assert_eq!(linspace(2.0, 5.0, 0).collect::<Vec<f64>>(), vec![]);
+        assert_eq!(linspace(0.0, 0.1, 11).last(), Some(0.1));
+        assert_eq!(linspace(0.0, 1.0, 1000).size_hint(), (1000, Some(1000)));
+        assert_eq!(linspace(0.0, 1.0, 1000).len(), 1000);
+    }
+
+    #[test]
+    fn test_linspace_no_drift() {
+        // Accumulating 0.1 ten thousand times drifts by around 1e-9.
+        let x = linspace(0.0, 1000.0, 10001).nth(9999).unwrap();
+        assert!((x - 999.9f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_wide_spans() {
+        assert_eq!(linspace(-1.7e308, 1.7e308, 3).collect::<Vec<f64>>(),
+                   vec![-1.7e308, 0.0, 1.7e308]);
+        assert_eq!(linspace(-1.7e308, 1.7e308, 2).collect::<Vec<f64>>(),
+                   vec![-1.7e308, 1.7e308]);
+        assert!(linspace(-f64::MAX, f64::MAX, 101).all(|x: f64| x.is_finite()));
+        assert_eq!(arange(-1.5e308, 1.5e308, 1e308).collect::<Vec<f64>>(),
+                   vec![-1.5e308, -0.5e308, 0.5e308]);
+        assert_eq!(arange(1.5e308, -1.5e308, -1e308).rev().collect::<Vec<f64>>(),
+                   vec![-0.5e308, 0.5e308, 1.5e308]);
+    }
+
+    #[test]
+    #[should_panic(expected = "finite")]
+    fn test_linspace_nan() {
+        linspace(0.0, f64::NAN, 3);
+    }
+
+    #[test]
+    fn test_arange() {
+        assert_eq!(arange(0.0, 1.0, 0.25).collect::<Vec<f64>>(), vec![0.0, 0.25, 0.5, 0.75]);
+        assert_eq!(arange(1.0, 0.0, -0.5).collect::<Vec<f64>>(), vec![1.0, 0.5]);
+        assert_eq!(arange(0.0, 1.0, -0.5).count(), 0);
+        assert_eq!(arange(1.0, 1.0, 0.5).count(), 0);
+        assert_eq!(arange(0.0, 1.0, 0.1).count(), 10);
+        assert_eq!(arange(0.0, 0.3, 0.1).count(), 3);
+        assert!(arange(0.0, 1.0, 0.1).all(|x: f32| x < 1.0));
+        assert_eq!(arange(0.0, 1.0, 0.3).size_hint(), (4, Some(4)));
+    }
+
+    #[test]
+    #[should_panic(expected = "step must not be zero")]
+    fn test_arange_zero_step() {
+        arange(0.0, 1.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "finite")]
+    fn test_arange_infinite() {
+        arange(0.0, f64::INFINITY, 1.0);
+    }
+
+    #[test]
+    fn test_geomspace() {
+        let v: Vec<f64> = geomspace(1.0, 1000.0, 4).collect();
+        assert_eq!(v[0], 1.0);
+        assert!((v[1] - 10.0).abs() < 1e-12);
+        assert!((v[2] - 100.0).abs() < 1e-12);
+        assert_eq!(v[3], 1000.0);
+
+        let v: Vec<f64> = geomspace(-1.0, -16.0, 5).collect();
+        assert_eq!(v[0], -1.0);
+        assert!((v[2] + 4.0).abs() < 1e-12);
+        assert_eq!(v[4], -16.0);
+        assert_eq!(geomspace(3.0, 5.0, 1).collect::<Vec<f64>>(), vec![3.0]);
+        assert_eq!(geomspace(3.0, 5.0, 7).len(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "same sign")]
+    fn test_geomspace_sign() {
+        geomspace(-1.0, 1.0, 3);
+    }
+
+    #[test]
+    fn test_logspace() {
+        assert_eq!(logspace(0.0, 3.0, 4, 2.0).collect::<Vec<f64>>(), vec![1.0, 2.0, 4.0, 8.0]);
+        assert_eq!(logspace(0.0, 2.0, 3, 10.0).size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn test_rev() {
+        assert_eq!(range(0, 5).rev().collect::<Vec<i32>>(), vec![4, 3, 2, 1, 0]);
+        assert_eq!(range(5, 0).rev().count(), 0);
+        assert_eq!(range_inclusive(0u8, 255).rev().collect::<Vec<u8>>(),
+                   range_inclusive(0u8, 255).collect::<Vec<u8>>().into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(range_inclusive(-128i8, -126).rev().collect::<Vec<i8>>(), vec![-126, -127, -128]);
+        assert_eq!(range('a', 'd').rev().collect::<Vec<char>>(), vec!['c', 'b', 'a']);
+        assert_eq!(range_step(0, 20, 6).rev().collect::<Vec<i32>>(), vec![18, 12, 6, 0]);
+        assert_eq!(range_step(20, 0, -6).rev().collect::<Vec<i32>>(), vec![2, 8, 14, 20]);
+        assert_eq!(range_step(200u8, 255, 50).rev().collect::<Vec<u8>>(), vec![250, 200]);
+        assert_eq!(range_step_inclusive(0, 20, 5).rev().collect::<Vec<i32>>(),
+                   vec![20, 15, 10, 5, 0]);
+        assert_eq!(range_step_inclusive(127i8, -128, -128).rev().collect::<Vec<i8>>(),
+                   vec![-1, 127]);
+        assert_eq!(range_step(5, 0, 0).rev().take(2).collect::<Vec<i32>>(), vec![5, 5]);
+        assert_eq!(linspace(0.0, 1.0, 3).rev().collect::<Vec<f64>>(), vec![1.0, 0.5, 0.0]);
+        assert_eq!(arange(0.0, 1.0, 0.5).rev().collect::<Vec<f64>>(), vec![0.5, 0.0]);
+        assert_eq!(geomspace(1.0, 4.0, 3).rev().last(), Some(1.0));
+        assert_eq!(logspace(0.0, 2.0, 3, 2.0).rev().collect::<Vec<f64>>(), vec![4.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_rev_matches_forward() {
+        for start in -10i8..10 {
+            for stop in -10i8..10 {
+                for step in (-4i8..5).filter(|&s| s != 0) {
+                    let mut forward: Vec<i8> = range_step(start, stop, step).collect();
+                    forward.reverse();
+                    assert_eq!(range_step(start, stop, step).rev().collect::<Vec<i8>>(), forward);
+                    let mut forward: Vec<i8> = range_step_inclusive(start, stop, step).collect();
+                    forward.reverse();
+                    assert_eq!(range_step_inclusive(start, stop, step).rev().collect::<Vec<i8>>(),
+                               forward);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strided() {
+        assert_eq!(range(0, 10).skip(4).step_by(2).collect::<Vec<i32>>(), vec![4, 6, 8]);
+        assert_eq!(range(0u8, 255).skip(250).collect::<Vec<u8>>(), vec![250, 251, 252, 253, 254]);
+        assert_eq!(range(0u8, 255).skip(300).count(), 0);
+        assert_eq!(range_inclusive(0u8, 255).step_by(100).collect::<Vec<u8>>(), vec![0, 100, 200]);
+        assert_eq!(range_inclusive(250u8, 255).skip(5).collect::<Vec<u8>>(), vec![255]);
+        assert_eq!(range_step(0, 20, 3).skip(2).step_by(2).collect::<Vec<i32>>(), vec![6, 12, 18]);
+        assert_eq!(range_step(20, 0, -3).nth(2), Some(14));
+        assert_eq!(range_step_inclusive(0u8, 255, 5).nth(51), Some(255));
+        assert_eq!(range_step_inclusive(0u8, 255, 5).nth(52), None);
+        assert_eq!(range_step(5, 0, 0).nth(3), Some(5));
+        assert_eq!(linspace(0.0, 1.0, 5).step_by(2).collect::<Vec<f64>>(), vec![0.0, 0.5, 1.0]);
+        assert_eq!(arange(0.0, 3.0, 1.0).skip(1).collect::<Vec<f64>>(), vec![1.0, 2.0]);
+        assert_eq!(geomspace(1.0, 16.0, 5).nth(4), Some(16.0));
+        assert_eq!(logspace(0.0, 4.0, 5, 2.0).skip(3).collect::<Vec<f64>>(), vec![8.0, 16.0]);
+    }
+
+    #[test]
+    fn test_strided_matches_walking() {
+        for start in -6i8..6 {
+            for stop in -6i8..6 {
+                for step in (-3i8..4).filter(|&s| s != 0) {
+                    for skip in 0..5 {
+                        for by in 1..4 {
+                            let walked: Vec<i8> = range_step(start, stop, step).filter(|_| true)
+                                .skip(skip).step_by(by).collect();
+                            assert_eq!(range_step(start, stop, step).skip(skip).step_by(by)
+                                       .collect::<Vec<i8>>(), walked);
+                            let walked: Vec<i8> = range_step_inclusive(start, stop, step)
+                                .filter(|_| true).skip(skip).step_by(by).collect();
+                            assert_eq!(range_step_inclusive(start, stop, step).skip(skip)
+                                       .step_by(by).collect::<Vec<i8>>(), walked);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_indexed() {
+        let mut r = range(10u8, 20);
+        assert_eq!(r.get(3), 13);
+        let mut r = range_inclusive(-3i8, 3);
+        assert_eq!(r.get(6), 3);
+        let mut r = range_step(10i32, 0, -3);
+        assert_eq!((r.len(), r.get(3)), (4, 1));
+        let mut r = range_step_inclusive(-8i8, 8, 4);
+        assert_eq!((r.len(), r.get(4)), (5, 8));
+        assert_eq!(range_step(0u8, 10, 4).zip(range(0u8, 10)).collect::<Vec<_>>(),
+                   vec![(0, 0), (4, 1), (8, 2)]);
+        let mut l = linspace(0.0, 1.0, 3);
+        assert_eq!(l.get(2), 1.0);
+        assert_eq!(range(0u8, 4).zip(linspace(0.0, 3.0, 4)).collect::<Vec<(u8, f64)>>(),
+                   vec![(0, 0.0), (1, 1.0), (2, 2.0), (3, 3.0)]);
+    }
+
     #[test]
     fn test_iterate() {
         assert_eq!(iterate(1, |x| x * 2).take(5).collect::<Vec<i32>>(), vec![1, 2, 4, 8, 16]);
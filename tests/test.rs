@@ -28,3 +28,39 @@ fn quickcheck_filter() {
 
     quickcheck::quickcheck(prop as fn(Vec<u32>) -> bool);
 }
+
+#[test]
+fn downstream_step() {
+    use traverse::utils::{range, range_inclusive, Step};
+
+    #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+    struct Id(u16);
+
+    impl Step for Id {
+        fn steps_between(start: &Id, end: &Id) -> Option<usize> {
+            u16::steps_between(&start.0, &end.0)
+        }
+
+        fn forward(self, n: usize) -> Option<Id> { self.0.forward(n).map(Id) }
+
+        fn backward(self, n: usize) -> Option<Id> { self.0.backward(n).map(Id) }
+    }
+
+    let ids: Vec<Id> = range(Id(3), Id(6)).collect();
+    assert_eq!(ids, vec![Id(3), Id(4), Id(5)]);
+    assert_eq!(range_inclusive(Id(65534), Id(65535)).count(), 2);
+    assert_eq!(range(Id(0), Id(10)).size_hint(), (10, Some(10)));
+}
+
+#[test]
+fn quickcheck_skip_step_by() {
+    fn prop(vec: Vec<u32>, skip: usize, step: usize) -> bool {
+        let (skip, step) = (skip % 20, step % 5 + 1);
+        let expected: Vec<&u32> = vec.iter().skip(skip).step_by(step).collect();
+        let result: Vec<&u32> = vec.skip(skip).step_by(step).collect();
+        let walked: Vec<&u32> = vec.filter(|_| true).skip(skip).step_by(step).collect();
+        expected == result && expected == walked
+    }
+
+    quickcheck::quickcheck(prop as fn(Vec<u32>, usize, usize) -> bool);
+}